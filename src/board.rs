@@ -2,15 +2,24 @@ use js_sys::Array;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-use crate::console_log;
-use crate::game::GameMove;
-use crate::pieces::king::{KingCastleBoardState, KingCastleMoveResult};
-// use crate::console_log;
-use crate::parser::{MoveReader, MoveResult, MoveWriter};
+use crate::editor::{PositionIssue, PositionValidator};
+use crate::error::{ChessError, ChessResult};
+use crate::fen::Fen;
+use crate::material::Material;
+use crate::parser::{MoveEffect, MoveResult};
+use crate::pieces::attack::{AttackValidator, ControlMap};
+use crate::pieces::king::{
+    CastleFiles, KingCastleBoardState, KingCastleMoveResult, KingCastleValidator,
+};
 use crate::pieces::piece::{Piece, PieceColor, PieceState, PieceType};
-use crate::pieces::strategy::{MoveHandler, MoveValidator, PieceMoveStrategy, StrategyBuilder};
-use crate::pieces::util::get_piece_default;
+use crate::pieces::strategy::{
+    InvalidMoveReason, MoveHandler, MoveValidator, PieceMoveStrategy, StrategyBuilder,
+};
+use crate::pieces::util::{get_chess960_back_rank, get_piece_default};
+use crate::snapshot::Snapshot;
 use crate::tile::{Tile, TileColor, TileCoord, TileRank, TileState};
+use crate::variants::crazyhouse::{Pocket, Pockets};
+use crate::variants::variant::{CheckCount, Variant, VariantBuilder, VariantRules};
 
 #[wasm_bindgen]
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -18,34 +27,158 @@ pub struct Board {
     tiles: Vec<Tile>,
     last_en_passant: Option<TileCoord>,
     king_castle_state: KingCastleBoardState,
+    active_color: PieceColor,
+    /// plies since the last pawn move or take
+    halfmove_clock: u32,
+    /// starts at 1, incremented after each black move
+    fullmove_number: u32,
+    variant: Variant,
+    /// checks given by each color, used by three check variant
+    check_count: CheckCount,
+    /// pieces which can be dropped, used by drop variants
+    pockets: Pockets,
+    /// coords of pieces which were promoted from pawns
+    promoted_coords: Vec<TileCoord>,
+    /// id given to the next new piece, see `Piece::id`
+    next_piece_id: u32,
 }
 
 #[wasm_bindgen]
 impl Board {
     pub fn new() -> Self {
-        let mut tiles = Vec::new();
+        let mut board = Self::empty();
 
-        let mut board = Self {
-            tiles: vec![],
-            last_en_passant: None,
-            king_castle_state: KingCastleBoardState::default(),
-        };
-
-        let mut idx: u8 = 0;
-        for row in 0..8 {
-            for col in 0..8 {
-                let coord = TileCoord::new(row, col);
-                let piece = get_piece_default(coord.row(), coord.col());
-                let tile = Tile::new(coord, idx, TileState::Inactive, piece);
-                tiles.push(tile);
-                idx += 1;
+        for idx in 0..64_usize {
+            let coord: TileCoord = idx.into();
+            if let Some(piece) = get_piece_default(coord.row(), coord.col()) {
+                board.set_new_tile(&coord, Some(piece.piece_type()), Some(piece.color()));
             }
         }
 
-        board.set_tiles(tiles);
         board
     }
 
+    /// create new chess960 board from start position index 0..960
+    /// index 518 is the standard start position
+    pub fn new_chess960(index: u16) -> Result<Board, JsValue> {
+        let back_rank =
+            get_chess960_back_rank(index).ok_or(ChessError::Chess960IndexOutOfBounds(index))?;
+
+        let mut board = Self::empty();
+        let mut files = CastleFiles::default();
+        let mut rook_cols = vec![];
+
+        for (col, piece_type) in back_rank.into_iter().enumerate() {
+            let col = col as u8;
+            for (row, piece_color) in [(0, PieceColor::White), (7, PieceColor::Black)] {
+                board.set_new_tile(
+                    &TileCoord::new(row, col),
+                    Some(piece_type),
+                    Some(piece_color),
+                );
+            }
+
+            match piece_type {
+                PieceType::King => files.king_col = col,
+                PieceType::Rook => rook_cols.push(col),
+                _ => {}
+            }
+        }
+
+        for col in 0..8 {
+            board.set_new_tile(
+                &TileCoord::new(1, col),
+                Some(PieceType::Pawn),
+                Some(PieceColor::White),
+            );
+            board.set_new_tile(
+                &TileCoord::new(6, col),
+                Some(PieceType::Pawn),
+                Some(PieceColor::Black),
+            );
+        }
+
+        // rooks are always either side of the king
+        files.long_rook_col = rook_cols[0];
+        files.short_rook_col = rook_cols[1];
+
+        let mut castle_state = KingCastleBoardState::new();
+        castle_state.chess960 = true;
+        castle_state.files = files;
+        board.set_king_castle_state(castle_state);
+
+        Ok(board)
+    }
+
+    /// create new board with the variant start position
+    pub fn new_variant(variant: Variant) -> Result<Board, JsValue> {
+        Ok(Self::variant_start(variant)?)
+    }
+
+    /// create new board from FEN string
+    pub fn from_fen(fen: &str) -> Result<Board, JsValue> {
+        Ok(Fen::parse(fen)?)
+    }
+
+    /// create new variant board from FEN string,
+    /// FEN may contain extra variant fields, eg. three check "3+3"
+    pub fn from_variant_fen(fen: &str, variant: Variant) -> Result<Board, JsValue> {
+        Ok(Fen::parse_variant(fen, variant)?)
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn check_count(&self) -> CheckCount {
+        self.check_count
+    }
+
+    pub fn set_check_count(&mut self, check_count: CheckCount) {
+        self.check_count = check_count
+    }
+
+    /// winner by variant win condition, eg. king of the hill
+    /// checkmate is not included
+    pub fn variant_winner(&self) -> Option<PieceColor> {
+        self.variant_rules().winner(self)
+    }
+
+    /// returns FEN string of the current position
+    pub fn to_fen(&self) -> String {
+        Fen::write(self)
+    }
+
+    /// returns Shredder-FEN string of the current position
+    /// castling rights are written as rook files, used for chess960
+    pub fn to_shredder_fen(&self) -> String {
+        Fen::write_shredder(self)
+    }
+
+    /// 64 bit FNV-1a hash of the position, ie. FEN without move clocks
+    /// used to compare positions
+    pub fn position_hash(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in Fen::write_position(self).bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
+
+    /// color of the player to move next
+    pub fn active_color(&self) -> PieceColor {
+        self.active_color
+    }
+
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
     // JS methods
 
     /// Returns JS array cloned copy of current tiles
@@ -54,16 +187,28 @@ impl Board {
         self.tiles.clone().into_iter().map(JsValue::from).collect()
     }
 
+    /// compact encoding of the board used in the render loop,
+    /// returned as a Uint8Array, see `Snapshot` for the layout
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        Snapshot::encode(self)
+    }
+
+    /// create board from bytes written with `to_snapshot`
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Board, JsValue> {
+        let board = Snapshot::decode(bytes)?;
+        Ok(board)
+    }
+
     /// return JS type of the piece
     /// mainly used for debugging purpose
-    pub fn js_piece(&self, coord: &TileCoord) -> JsValue {
+    pub fn js_piece(&self, coord: &TileCoord) -> Result<JsValue, JsValue> {
         let tile = self.get_tile(coord);
         match tile {
             Some(tile) => match tile.piece() {
                 Some(piece) => piece.to_json(),
-                None => false.into(),
+                None => Ok(false.into()),
             },
-            None => false.into(),
+            None => Ok(false.into()),
         }
     }
 
@@ -76,6 +221,7 @@ impl Board {
 
     // tile methods
 
+    /// set new piece with a new id on tile, or clear tile if None
     pub fn set_new_tile(
         &mut self,
         coord: &TileCoord,
@@ -86,28 +232,99 @@ impl Board {
         let piece = if piece_type.is_none() || piece_color.is_none() {
             None
         } else {
-            Some(Piece::new(
-                piece_type.unwrap(),
-                piece_color.unwrap(),
-                *coord,
-            ))
+            let mut piece = Piece::new(piece_type.unwrap(), piece_color.unwrap(), *coord);
+            piece.set_id(self.next_piece_id);
+            self.next_piece_id += 1;
+            Some(piece)
         };
 
-        // get tile idx
-        let tile_idx = Board::tile_idx_from_coord(&coord.clone());
+        self.write_tile(coord, piece);
+    }
 
-        // create new tile
-        let new_tile = Tile::new(*coord, tile_idx as u8, TileState::Inactive, piece);
+    /// replace piece on coord with promoted piece of the same color
+    /// promoted pieces revert to pawns when taken in drop variants
+    /// returns false if there is no piece on the coord
+    pub fn promote_piece(&mut self, coord: &TileCoord, piece_type: PieceType) -> bool {
+        let piece = match self.get_piece(coord) {
+            Some(piece) => piece,
+            None => return false,
+        };
 
-        // write new tile to board array
-        self.tiles[tile_idx] = new_tile;
+        // promoted piece keeps the id of the pawn
+        let mut promoted = Piece::new(piece_type, piece.color(), *coord);
+        promoted.set_id(piece.id());
+        self.place_piece(coord, promoted);
+        self.promoted_coords.push(*coord);
+        true
     }
 
-    pub fn set_tile_state(&mut self, coord: &TileCoord, state: TileState) {
-        // set selected tile coord as selected
-        let idx = Board::tile_idx_from_coord(coord);
-        let tile = &mut self.tiles[idx];
-        tile.set_state(state);
+    pub fn is_promoted(&self, coord: &TileCoord) -> bool {
+        self.promoted_coords.contains(coord)
+    }
+
+    /// drop piece from pocket onto empty tile, used by drop variants
+    /// unlike `move_piece` the result includes check and checkmate flags
+    pub fn drop_piece(
+        &mut self,
+        piece_type: PieceType,
+        piece_color: PieceColor,
+        coord: &TileCoord,
+    ) -> Option<MoveResult> {
+        let move_validator = MoveValidator::new(*coord, self);
+        if !move_validator.is_valid_drop(piece_type, piece_color) {
+            return None;
+        }
+
+        self.pockets.pocket_mut(piece_color).remove(piece_type);
+        self.set_new_tile(coord, Some(piece_type), Some(piece_color));
+        self.set_last_en_passant(None);
+
+        let enemy_piece_color = PieceColor::opposite_color(piece_color);
+        let move_result = MoveResult {
+            piece_type,
+            piece_color,
+            from_coord: *coord,
+            to_coord: *coord,
+            promote_piece_type: None,
+            is_promote_piece: false,
+            is_take: false,
+            is_short_castle: false,
+            is_long_castle: false,
+            is_check: MoveValidator::is_check(enemy_piece_color, self),
+            is_checkmate: MoveValidator::is_checkmate(enemy_piece_color, self),
+            is_from_remote: false,
+            is_drop: true,
+            pocket_piece_type: None,
+            effects: vec![MoveEffect::dropped(piece_type, piece_color, *coord)],
+        };
+
+        self.king_castle_state.update_state(&*self);
+        self.variant_rules().update_state(&move_result, self);
+        self.update_move_clocks(&move_result);
+
+        self.set_last_move(coord, coord);
+        self.update_check_markers();
+
+        Some(move_result)
+    }
+
+    /// pieces which can be dropped by piece color
+    pub fn pocket(&self, piece_color: PieceColor) -> Pocket {
+        *self.pockets.pocket(piece_color)
+    }
+
+    pub fn pockets(&self) -> Pockets {
+        self.pockets
+    }
+
+    /// add piece to pocket, used to pass pieces between bughouse boards
+    pub fn add_pocket_piece(&mut self, piece_type: PieceType, piece_color: PieceColor) {
+        self.pockets.pocket_mut(piece_color).add(piece_type)
+    }
+
+    pub fn set_tile_state(&mut self, coord: &TileCoord, state: TileState) -> Result<(), JsValue> {
+        self.update_tile_state(coord, state)?;
+        Ok(())
     }
 
     pub fn get_selected_piece_coord(&self) -> Option<TileCoord> {
@@ -122,25 +339,33 @@ impl Board {
         None
     }
 
+    /// clear selected tile, highlights and other tile states are kept
     pub fn clear_active_tiles(&mut self) {
-        // clear all tile selected states
-        for tile in self.tiles.iter_mut() {
-            tile.set_state(TileState::Inactive)
-        }
+        self.clear_tile_states(TileState::Active)
     }
 
+    /// clear quiet and capture move highlights
     pub fn clear_highlights(&mut self) {
-        // clear all highlighted tiles
+        self.clear_tile_states(TileState::Highlight);
+        self.clear_tile_states(TileState::Capture);
+    }
+
+    /// set all tiles with state back to inactive
+    pub fn clear_tile_states(&mut self, state: TileState) {
         for tile in self.tiles.iter_mut() {
-            tile.set_state(TileState::Inactive)
+            if tile.state() == state {
+                tile.set_state(TileState::Inactive)
+            }
         }
     }
 
+    /// highlight valid moves of piece at coord,
+    /// moves which take a piece are highlighted as capture
     pub fn highlight_moves(&mut self, coord: TileCoord) {
         if let Some(piece) = self.get_piece(&coord) {
             let mut valid_moves = vec![];
             // create new piece strategy based on piece type
-            let piece_strategy = self.new_piece_strategy(piece);
+            let piece_strategy = self.new_piece_strategy(piece.clone());
 
             for tile in piece_strategy.moves() {
                 if tile.in_bounds() {
@@ -153,18 +378,84 @@ impl Board {
                     if validator.is_valid_move(piece_strategy.as_ref(), false)
                         && !validator.is_king_take()
                     {
-                        valid_moves.push(new_coord)
+                        // own piece on new coord is a castle move
+                        let is_capture = self
+                            .peek_tile(&new_coord)
+                            .is_some_and(|taken| taken.color() != piece.color())
+                            || validator.is_en_passant_take(piece_strategy.as_ref());
+
+                        valid_moves.push((new_coord, is_capture))
                     }
                 }
             }
 
             // update tile state to be highlighted
-            for coord in valid_moves {
-                self.tiles[Board::tile_idx_from_coord(&coord)].set_state(TileState::Highlight)
+            for (coord, is_capture) in valid_moves {
+                let state = if is_capture {
+                    TileState::Capture
+                } else {
+                    TileState::Highlight
+                };
+                self.tiles[Board::tile_idx_from_coord(&coord)].set_state(state)
+            }
+        }
+    }
+
+    // tile marker methods, see `TileMarkers`
+
+    /// mark from and to coords of the last move, replacing the previous marks
+    pub fn set_last_move(&mut self, from_coord: &TileCoord, to_coord: &TileCoord) {
+        self.clear_last_move();
+        if let Ok(tile) = self.get_tile_mut(from_coord) {
+            tile.markers_mut().last_move_from = true;
+        }
+        if let Ok(tile) = self.get_tile_mut(to_coord) {
+            tile.markers_mut().last_move_to = true;
+        }
+    }
+
+    pub fn clear_last_move(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            tile.markers_mut().last_move_from = false;
+            tile.markers_mut().last_move_to = false;
+        }
+    }
+
+    /// mark hovered tile, None clears the hover mark
+    pub fn set_hover(&mut self, coord: Option<TileCoord>) {
+        self.clear_hover();
+        if let Some(tile) = coord.and_then(|coord| self.get_tile_mut(&coord).ok()) {
+            tile.markers_mut().hover = true;
+        }
+    }
+
+    pub fn clear_hover(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            tile.markers_mut().hover = false;
+        }
+    }
+
+    /// mark tiles of kings in check, updated after each move
+    pub fn update_check_markers(&mut self) {
+        self.clear_check();
+        for piece_color in [PieceColor::White, PieceColor::Black] {
+            if !MoveValidator::is_check(piece_color, self) {
+                continue;
+            }
+            if let Some(king_coord) = AttackValidator::king_coord(piece_color, self) {
+                self.tiles[Board::tile_idx_from_coord(&king_coord)]
+                    .markers_mut()
+                    .check = true;
             }
         }
     }
 
+    pub fn clear_check(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            tile.markers_mut().check = false;
+        }
+    }
+
     pub fn clear_tile(&mut self, row: u8, col: u8) {
         let coord = TileCoord::new(row, col);
         self.set_new_tile(&coord, None, None);
@@ -177,28 +468,82 @@ impl Board {
     }
 
     /// main public method used to get move result without updating board,
-    /// updates board with new pieces
+    /// promote piece type may be None to preview a promotion move,
+    /// the result then has is_promote_piece set and the move
+    /// can be made once the promote piece is chosen
     pub fn pre_move_result(
         &mut self,
         old_coord: TileCoord,
         new_coord: TileCoord,
+        promote_piece_type: Option<PieceType>,
     ) -> Option<MoveResult> {
         // do not ignore check on main method to move pieces
         // board is NOT updated with new pieces after this method
 
         self.clone()
-            .handle_move_piece(old_coord, new_coord, false, false)
+            .handle_move_piece(old_coord, new_coord, promote_piece_type, false, false)
+    }
+
+    /// reason moving piece from old coord to new coord is invalid,
+    /// None if the move is valid, used to show helpful messages to the player
+    pub fn explain_move(
+        &self,
+        old_coord: TileCoord,
+        new_coord: TileCoord,
+    ) -> Option<InvalidMoveReason> {
+        if !old_coord.in_bounds() || !new_coord.in_bounds() {
+            return Some(InvalidMoveReason::OutOfBounds);
+        }
+
+        let piece = match self.get_piece(&old_coord) {
+            Some(piece) => piece,
+            None => return Some(InvalidMoveReason::NoPiece),
+        };
+
+        if piece.color() != self.active_color {
+            return Some(InvalidMoveReason::NotYourTurn);
+        }
+
+        let piece_strategy = self.new_piece_strategy(piece);
+        MoveValidator::new(new_coord, self)
+            .validate_move(piece_strategy.as_ref(), false)
+            .err()
+    }
+
+    /// message explaining why the move is invalid, see `explain_move`
+    pub fn explain_move_message(
+        &self,
+        old_coord: TileCoord,
+        new_coord: TileCoord,
+    ) -> Option<String> {
+        self.explain_move(old_coord, new_coord)
+            .map(|reason| reason.to_string())
     }
 
     /// main public method used to move pieces,
     /// updates board with new pieces
-    pub fn move_piece(&mut self, old_coord: TileCoord, new_coord: TileCoord) -> Option<MoveResult> {
+    /// promote piece type is required when moving a pawn to the last rank
+    pub fn move_piece(
+        &mut self,
+        old_coord: TileCoord,
+        new_coord: TileCoord,
+        promote_piece_type: Option<PieceType>,
+    ) -> Option<MoveResult> {
         // do not ignore check on main method to move pieces
         // board is updated with new pieces after this method
-        let result = self.handle_move_piece(old_coord, new_coord, false, true);
+        let result = self.handle_move_piece(old_coord, new_coord, promote_piece_type, false, true);
 
-        // update king castle state after move is completed
-        self.king_castle_state.update_state(&*self);
+        if let Some(move_result) = &result {
+            // update king castle state after move is completed
+            self.king_castle_state.update_rights(old_coord, new_coord);
+            self.king_castle_state.update_state(&*self);
+
+            self.variant_rules().update_state(move_result, self);
+            self.update_move_clocks(move_result);
+
+            self.set_last_move(&old_coord, &new_coord);
+            self.update_check_markers();
+        }
 
         result
     }
@@ -210,7 +555,7 @@ impl Board {
         let new_coord: TileCoord = TileCoord::new(new_row, new_col);
         // ignore check flag active
         // method used to check if king is in check
-        self.handle_move_piece(old_coord, new_coord, true, true);
+        self.handle_move_piece(old_coord, new_coord, None, true, true);
     }
 
     /// used as base move command to move pieces
@@ -221,6 +566,7 @@ impl Board {
         &mut self,
         old_coord: TileCoord,
         new_coord: TileCoord,
+        promote_piece_type: Option<PieceType>,
         ignore_check: bool,
         update_board: bool,
     ) -> Option<MoveResult> {
@@ -254,18 +600,79 @@ impl Board {
         // move validator
         let move_validator = MoveValidator::new(new_coord, self);
 
+        // king moving onto own rook in chess960 is a castle, not a take
+        let is_castle = move_validator
+            .castle_side(piece_strategy.as_ref())
+            .is_some();
+
         // check if piece take
-        let is_take =
-            move_validator.is_take() | move_validator.is_en_passant_take(piece_strategy.as_ref());
+        let is_take = !is_castle
+            && (move_validator.is_take()
+                | move_validator.is_en_passant_take(piece_strategy.as_ref()));
 
         // check if king take
         let is_king_take = move_validator.is_king_take();
 
+        // piece added to a pocket in drop variants,
+        // promoted pieces revert to pawns when taken
+        let pocket_piece_type = if is_take && self.variant_rules().has_pockets() {
+            if self.is_promoted(&new_coord)
+                || move_validator.is_en_passant_take(piece_strategy.as_ref())
+            {
+                Some(PieceType::Pawn)
+            } else {
+                self.get_piece(&new_coord).map(|piece| piece.piece_type())
+            }
+        } else {
+            None
+        };
+        let is_promoted = self.is_promoted(&old_coord);
+
         // only continue if move is valid
         if !move_validator.is_valid_move(piece_strategy.as_ref(), ignore_check) {
             return None;
         }
 
+        let is_promote_piece = self.is_promote_piece(new_coord, piece.piece_type(), piece.color());
+
+        // promote piece must be chosen when moving pawn to the last rank
+        // moves used to validate check promote to a queen
+        let promote_piece_type = match (is_promote_piece, promote_piece_type) {
+            (true, Some(piece_type)) => {
+                if !self
+                    .variant_rules()
+                    .promote_piece_types()
+                    .contains(&piece_type)
+                {
+                    return None;
+                }
+                Some(piece_type)
+            }
+            (true, None) if ignore_check => Some(PieceType::Queen),
+            (true, None) if update_board => return None,
+            (false, Some(_)) => return None,
+            _ => None,
+        };
+
+        // board changes made by the move, in the order they are applied
+        let mut effects = vec![];
+        let enemy_piece_color = PieceColor::opposite_color(piece.color());
+        if move_validator.is_en_passant_take(piece_strategy.as_ref()) {
+            if let Some(last_en_passant_coord) = self.last_en_passant() {
+                effects.push(MoveEffect::removed(
+                    PieceType::Pawn,
+                    enemy_piece_color,
+                    last_en_passant_coord,
+                ));
+            }
+        } else if let Some(taken) = self.get_piece(&new_coord).filter(|_| is_take) {
+            effects.push(MoveEffect::removed(
+                taken.piece_type(),
+                taken.color(),
+                new_coord,
+            ));
+        }
+
         // if en passant take clear en passant coord
         if move_validator.is_en_passant_take(piece_strategy.as_ref()) {
             if let Some(last_en_passant_coord) = self.last_en_passant() {
@@ -290,23 +697,77 @@ impl Board {
             king_castle_result = move_handler.handle_king_castle_move(piece_strategy.as_ref());
         }
 
-        let is_promote_piece = self.is_promote_piece(new_coord, piece.piece_type(), piece.color());
+        // castle moves the king to its castle coord,
+        // which in chess960 may differ from the requested coord
+        let to_coord = match &king_castle_result {
+            Some(side) => KingCastleValidator::castle_coord(piece.color(), side),
+            None => new_coord,
+        };
+
+        match &king_castle_result {
+            Some(side) => {
+                let rook_coord = self.king_castle_state.rook_start_coord(piece.color(), side);
+                let rook_to_coord = KingCastleValidator::rook_castle_coord(piece.color(), side);
+
+                // in chess960 the king or rook may not move
+                for (piece_type, from_coord, to_coord) in [
+                    (PieceType::King, old_coord, to_coord),
+                    (PieceType::Rook, rook_coord, rook_to_coord),
+                ] {
+                    if from_coord != to_coord {
+                        effects.push(MoveEffect::moved(
+                            piece_type,
+                            piece.color(),
+                            from_coord,
+                            to_coord,
+                        ));
+                    }
+                }
+            }
+            None => {
+                effects.push(MoveEffect::moved(
+                    piece.piece_type(),
+                    piece.color(),
+                    old_coord,
+                    new_coord,
+                ));
+
+                if let Some(piece_type) = promote_piece_type {
+                    effects.push(MoveEffect::promoted(piece_type, piece.color(), new_coord));
+                }
+            }
+        }
 
         // return early if NOT updated_board flag
         // used to validate if king in check or checkmate
         // or able to move out of check or checkmate
         if !update_board {
-            let enemy_piece_color = PieceColor::opposite_color(piece_strategy.color());
             // make move to see if possible check or checkmate
+            // castle move has already placed king and rook
+            // previewed promotion without promote piece is checked as a queen
             let mut board_copy = self.clone();
-            board_copy.move_piece(old_coord, new_coord);
+            if king_castle_result.is_none() {
+                let check_promote_piece_type = match is_promote_piece {
+                    true => promote_piece_type.or(Some(PieceType::Queen)),
+                    false => None,
+                };
+
+                // variant board changes are only known once the move is made
+                if let Some(move_result) =
+                    board_copy.move_piece(old_coord, new_coord, check_promote_piece_type)
+                {
+                    if promote_piece_type.is_some() || !is_promote_piece {
+                        effects = move_result.effects;
+                    }
+                }
+            }
 
             return Some(MoveResult {
                 piece_type: piece_strategy.piece_type(),
                 piece_color: piece_strategy.color(),
                 from_coord: old_coord,
-                to_coord: new_coord,
-                promote_piece_type: None,
+                to_coord,
+                promote_piece_type,
                 is_promote_piece,
                 is_take,
                 is_short_castle: king_castle_result == Some(KingCastleMoveResult::ShortCastle),
@@ -314,24 +775,40 @@ impl Board {
                 is_check: MoveValidator::is_check(enemy_piece_color, &board_copy),
                 is_checkmate: MoveValidator::is_checkmate(enemy_piece_color, &board_copy),
                 is_from_remote: false,
+                is_drop: false,
+                pocket_piece_type,
+                effects,
             });
         }
 
         // only clear tiles if not king take, ie. cannot take king off board
         // update board
         if !is_king_take {
-            // clear old tile
-            self.clear_tile(old_coord.row(), old_coord.col());
+            // castle move handler has already placed king and rook
+            if king_castle_result.is_none() {
+                // clear old tile
+                self.clear_tile(old_coord.row(), old_coord.col());
 
-            // set new tile
-            self.set_new_tile(&new_coord, Some(piece.piece_type()), Some(piece.color()));
+                // set new tile, moved piece keeps its id
+                self.place_piece(&new_coord, piece.clone());
 
-            return Some(MoveResult {
+                // promoted piece stays promoted after moving
+                if is_promoted {
+                    self.promoted_coords.push(new_coord);
+                }
+
+                // replace pawn with promote piece
+                if let Some(piece_type) = promote_piece_type {
+                    self.promote_piece(&new_coord, piece_type);
+                }
+            }
+
+            let mut move_result = MoveResult {
                 piece_type: piece_strategy.piece_type(),
                 piece_color: piece_strategy.color(),
                 from_coord: old_coord,
-                to_coord: new_coord,
-                promote_piece_type: None,
+                to_coord,
+                promote_piece_type,
                 is_promote_piece,
                 is_take,
                 is_short_castle: king_castle_result == Some(KingCastleMoveResult::ShortCastle),
@@ -340,7 +817,15 @@ impl Board {
                 is_check: false,
                 is_checkmate: false,
                 is_from_remote: false,
-            });
+                is_drop: false,
+                pocket_piece_type,
+                effects,
+            };
+
+            // variant specific board changes, eg. atomic explosions
+            self.variant_rules().handle_move(&mut move_result, self);
+
+            return Some(move_result);
         }
 
         // if no piece is moved
@@ -356,13 +841,12 @@ impl Board {
         }
     }
 
-    pub fn set_piece_state(&mut self, coord: &TileCoord, piece_state: PieceState) {
-        // set selected tile coord as selected
-        let idx = Board::tile_idx_from_coord(coord);
-        let tile = &mut self.tiles[idx];
-        if tile.piece().is_some() {
-            tile.set_piece_state(piece_state)
-        };
+    pub fn set_piece_state(
+        &mut self,
+        coord: &TileCoord,
+        piece_state: PieceState,
+    ) -> Result<(), JsValue> {
+        Ok(self.update_piece_state(coord, piece_state)?)
     }
 
     pub fn peek_tile(&self, coord: &TileCoord) -> Option<Piece> {
@@ -382,30 +866,59 @@ impl Board {
         self.last_en_passant
     }
 
-    pub fn tile_at_index(&self, index: usize) -> Tile {
-        self.tiles[index].clone()
+    pub fn tile_at_index(&self, index: usize) -> Result<Tile, JsValue> {
+        Ok(self.tile_by_index(index)?.clone())
     }
 
     pub fn num_tiles(&self) -> usize {
         self.tiles.len()
     }
 
+    pub fn set_active_color(&mut self, piece_color: PieceColor) {
+        self.active_color = piece_color
+    }
+
+    pub fn set_move_clocks(&mut self, halfmove_clock: u32, fullmove_number: u32) {
+        self.halfmove_clock = halfmove_clock;
+        self.fullmove_number = fullmove_number;
+    }
+
     pub fn is_checkmate(&self) -> Option<PieceColor> {
         // check each color if is in checkmate
-        for piece_color in [PieceColor::White, PieceColor::Black] {
-            // SAFETY:
-            // there is always a king on the board
-            let king_coord = self.get_king_coord(piece_color);
-            let king = self.get_piece(&king_coord).unwrap();
+        [PieceColor::White, PieceColor::Black]
+            .into_iter()
+            .find(|piece_color| MoveValidator::is_checkmate(*piece_color, self))
+    }
 
-            let piece_strategy = self.new_piece_strategy(king);
+    // attack methods
 
-            if MoveValidator::is_checkmate(piece_strategy.color(), self) {
-                return Some(piece_color);
-            }
-        }
+    /// returns JS array of coords of all pieces of piece color attacking coord
+    pub fn js_attackers_of(&self, coord: TileCoord, piece_color: PieceColor) -> Array {
+        self.attackers_of(coord, piece_color)
+            .into_iter()
+            .map(JsValue::from)
+            .collect()
+    }
 
-        None
+    /// check if any piece of by color attacks coord
+    pub fn is_attacked(&self, coord: TileCoord, by_color: PieceColor) -> bool {
+        AttackValidator::is_attacked(coord, by_color, self)
+    }
+
+    /// JS array of `PositionIssue` which make the position impossible to play,
+    /// used by the board editor, see `PositionValidator`
+    pub fn js_validate(&self) -> Result<Array, JsValue> {
+        Ok(self.validate()?.into_iter().map(JsValue::from).collect())
+    }
+
+    /// captured pieces and material value of both colors
+    pub fn material(&self) -> Result<Material, JsValue> {
+        Material::new(self)
+    }
+
+    /// number of white and black attackers of every tile
+    pub fn control_map(&self) -> ControlMap {
+        ControlMap::new(self)
     }
 
     // ---
@@ -413,7 +926,7 @@ impl Board {
     // ---
 
     pub fn tile_color_from_coord(coord: TileCoord) -> TileColor {
-        if (coord.col() + coord.row()) % 2 == 0 {
+        if (coord.col() + coord.row()).is_multiple_of(2) {
             TileColor::Black
         } else {
             TileColor::White
@@ -428,34 +941,57 @@ impl Board {
     // private methods
     // ---
 
-    fn set_tiles(&mut self, tiles: Vec<Tile>) {
-        self.tiles = tiles
-    }
+    /// write piece to tile, tile state is reset and tile markers are kept
+    fn write_tile(&mut self, coord: &TileCoord, piece: Option<Piece>) {
+        // get tile idx
+        let tile_idx = Board::tile_idx_from_coord(coord);
 
-    fn get_tile(&self, coord: &TileCoord) -> Option<&Tile> {
-        let idx = Board::tile_idx_from_coord(coord);
+        // create new tile
+        let mut new_tile = Tile::new(*coord, tile_idx as u8, TileState::Inactive, piece);
+        new_tile.set_markers(self.tiles[tile_idx].markers());
+
+        // write new tile to board array
+        self.tiles[tile_idx] = new_tile;
+
+        // new piece is not promoted, see `promote_piece`
+        self.promoted_coords.retain(|promoted| promoted != coord);
+    }
 
-        // ensure idx is valid, ie. on the board
-        if (0..64).contains(&idx) {
-            Some(&self.tiles[idx])
+    /// update side to move and move clocks after move is made
+    fn update_move_clocks(&mut self, move_result: &MoveResult) {
+        if move_result.piece_type == PieceType::Pawn || move_result.is_take {
+            self.halfmove_clock = 0;
         } else {
-            None
+            self.halfmove_clock += 1;
+        }
+
+        if move_result.piece_color == PieceColor::Black {
+            self.fullmove_number += 1;
         }
+
+        self.active_color = PieceColor::opposite_color(move_result.piece_color);
     }
 
-    fn get_king_coord(&self, piece_color: PieceColor) -> TileCoord {
-        for tile in &self.tiles {
-            if let Some(piece) = tile.piece() {
-                if piece.color() == piece_color && piece.piece_type() == PieceType::King {
-                    return tile.coord();
-                }
-            }
+    fn get_tile(&self, coord: &TileCoord) -> Option<&Tile> {
+        // ensure coord is valid, ie. on the board
+        if !coord.in_bounds() {
+            return None;
         }
 
-        // NOTE:
-        // this is never reached
-        // there is always a king on the board
-        TileCoord::new(1, 1)
+        let idx = Board::tile_idx_from_coord(coord);
+        self.tiles.get(idx)
+    }
+
+    fn get_tile_mut(&mut self, coord: &TileCoord) -> ChessResult<&mut Tile> {
+        // ensure coord is valid, ie. on the board
+        if !coord.in_bounds() {
+            return Err(ChessError::CoordOutOfBounds(*coord));
+        }
+
+        let idx = Board::tile_idx_from_coord(coord);
+        self.tiles
+            .get_mut(idx)
+            .ok_or(ChessError::CoordOutOfBounds(*coord))
     }
 
     fn is_promote_piece(
@@ -484,8 +1020,356 @@ impl Board {
     }
 }
 
+impl Board {
+    /// variant start position, see `new_variant`
+    pub fn variant_start(variant: Variant) -> ChessResult<Board> {
+        match VariantBuilder::new_variant_rules(variant).start_fen() {
+            Some(fen) => Fen::parse_variant(fen, variant),
+            None => {
+                let mut board = Board::new();
+                board.variant = variant;
+                Ok(board)
+            }
+        }
+    }
+
+    /// create new board without any pieces, white to move
+    pub fn empty() -> Self {
+        let tiles = (0..64_usize)
+            .map(|idx| Tile::new(idx.into(), idx as u8, TileState::Inactive, None))
+            .collect();
+
+        Self {
+            tiles,
+            last_en_passant: None,
+            king_castle_state: KingCastleBoardState::default(),
+            active_color: PieceColor::White,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            variant: Variant::Standard,
+            check_count: CheckCount::default(),
+            pockets: Pockets::default(),
+            promoted_coords: vec![],
+            next_piece_id: 1,
+        }
+    }
+
+    /// set state of tile at coord, see `set_tile_state`
+    pub fn update_tile_state(&mut self, coord: &TileCoord, state: TileState) -> ChessResult<()> {
+        let tile = self.get_tile_mut(coord)?;
+        tile.set_state(state);
+        Ok(())
+    }
+
+    /// set state of piece at coord, see `set_piece_state`
+    pub fn update_piece_state(
+        &mut self,
+        coord: &TileCoord,
+        piece_state: PieceState,
+    ) -> ChessResult<()> {
+        // set selected tile coord as selected
+        let tile = self.get_tile_mut(coord)?;
+        tile.update_piece_state(piece_state)
+    }
+
+    /// get tile by index, see `tile_at_index`
+    pub fn tile_by_index(&self, index: usize) -> ChessResult<&Tile> {
+        self.tiles
+            .get(index)
+            .ok_or(ChessError::TileIndexOutOfBounds(index))
+    }
+
+    /// place existing piece on tile keeping its id,
+    /// used to move pieces so they can be tracked by the frontend
+    pub fn place_piece(&mut self, coord: &TileCoord, mut piece: Piece) {
+        // keep ids of new pieces unique
+        self.next_piece_id = self.next_piece_id.max(piece.id() + 1);
+        piece.set_coord(*coord);
+        piece.set_state(PieceState::Unselected);
+        self.write_tile(coord, Some(piece));
+    }
+
+    pub fn set_king_castle_state(&mut self, king_castle_state: KingCastleBoardState) {
+        self.king_castle_state = king_castle_state
+    }
+
+    pub fn set_pockets(&mut self, pockets: Pockets) {
+        self.pockets = pockets
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant
+    }
+
+    pub fn variant_rules(&self) -> Box<dyn VariantRules> {
+        VariantBuilder::new_variant_rules(self.variant)
+    }
+
+    /// coords of all pieces of piece color attacking coord
+    pub fn attackers_of(&self, coord: TileCoord, piece_color: PieceColor) -> Vec<TileCoord> {
+        AttackValidator::attackers_of(coord, piece_color, self)
+    }
+
+    /// issues which make the position impossible to play, see `PositionValidator`
+    pub fn validate(&self) -> ChessResult<Vec<PositionIssue>> {
+        PositionValidator::validate(self)
+    }
+
+    /// returns error listing all position issues if the position cannot be played
+    pub fn check_position(&self) -> ChessResult<()> {
+        let issues = self.validate()?;
+        if issues.is_empty() {
+            return Ok(());
+        }
+
+        let messages: Vec<String> = issues.iter().map(|issue| issue.message()).collect();
+        Err(ChessError::InvalidPosition(messages.join(", ")))
+    }
+}
+
 impl Default for Board {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::error::ChessError;
+    use crate::parser::MoveEffect;
+    use crate::pieces::piece::{PieceColor, PieceState, PieceType};
+    use crate::pieces::strategy::InvalidMoveReason;
+    use crate::tile::{TileCoord, TileState};
+
+    #[test]
+    pub fn test_explain_move() {
+        let board = Board::new();
+        let explain = |board: &Board, from: (u8, u8), to: (u8, u8)| {
+            board.explain_move(TileCoord::new(from.0, from.1), TileCoord::new(to.0, to.1))
+        };
+
+        assert_eq!(None, explain(&board, (1, 4), (3, 4)));
+        assert_eq!(
+            Some(InvalidMoveReason::NoPiece),
+            explain(&board, (3, 4), (4, 4))
+        );
+        assert_eq!(
+            Some(InvalidMoveReason::NotYourTurn),
+            explain(&board, (6, 4), (4, 4))
+        );
+        assert_eq!(
+            Some(InvalidMoveReason::PathBlocked),
+            explain(&board, (0, 0), (3, 0))
+        );
+        assert_eq!(
+            Some(InvalidMoveReason::OwnPieceTake),
+            explain(&board, (0, 0), (1, 0))
+        );
+        assert_eq!(
+            Some(InvalidMoveReason::InvalidPawnTake),
+            explain(&board, (1, 4), (2, 5))
+        );
+        assert_eq!(
+            Some(InvalidMoveReason::InvalidPieceMove),
+            explain(&board, (0, 6), (2, 6))
+        );
+
+        // bishop on e2 pinned by the rook on e8, rook on d8 guards d1
+        let board = Board::from_fen("3rr1k1/8/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            Some(InvalidMoveReason::PiecePinned),
+            explain(&board, (1, 4), (2, 3))
+        );
+        assert_eq!(
+            Some(InvalidMoveReason::KingInCheck),
+            explain(&board, (0, 4), (0, 3))
+        );
+
+        // rook on f8 attacks f1
+        let board = Board::from_fen("5rk1/8/8/8/8/8/8/R3K2R w Q - 0 1").unwrap();
+        assert_eq!(
+            Some(InvalidMoveReason::CastleRightsLost),
+            explain(&board, (0, 4), (0, 6))
+        );
+        let board = Board::from_fen("5rk1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        assert_eq!(
+            Some(InvalidMoveReason::CastleThroughCheck),
+            explain(&board, (0, 4), (0, 6))
+        );
+    }
+
+    #[test]
+    pub fn test_move_effects() {
+        let (white, black) = (PieceColor::White, PieceColor::Black);
+
+        // castle moves king and rook
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        let move_result = board
+            .move_piece(TileCoord::new(0, 4), TileCoord::new(0, 6), None)
+            .unwrap();
+        assert_eq!(
+            vec![
+                MoveEffect::moved(
+                    PieceType::King,
+                    white,
+                    TileCoord::new(0, 4),
+                    TileCoord::new(0, 6)
+                ),
+                MoveEffect::moved(
+                    PieceType::Rook,
+                    white,
+                    TileCoord::new(0, 7),
+                    TileCoord::new(0, 5)
+                ),
+            ],
+            move_result.effects
+        );
+
+        // en passant removes pawn beside the taking pawn
+        let mut board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let move_result = board
+            .move_piece(TileCoord::new(4, 4), TileCoord::new(5, 3), None)
+            .unwrap();
+        assert_eq!(
+            vec![
+                MoveEffect::removed(PieceType::Pawn, black, TileCoord::new(4, 3)),
+                MoveEffect::moved(
+                    PieceType::Pawn,
+                    white,
+                    TileCoord::new(4, 4),
+                    TileCoord::new(5, 3)
+                ),
+            ],
+            move_result.effects
+        );
+
+        // promotion with take
+        let mut board = Board::from_fen("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let move_result = board
+            .move_piece(
+                TileCoord::new(6, 4),
+                TileCoord::new(7, 3),
+                Some(PieceType::Knight),
+            )
+            .unwrap();
+        assert_eq!(
+            vec![
+                MoveEffect::removed(PieceType::Rook, black, TileCoord::new(7, 3)),
+                MoveEffect::moved(
+                    PieceType::Pawn,
+                    white,
+                    TileCoord::new(6, 4),
+                    TileCoord::new(7, 3)
+                ),
+                MoveEffect::promoted(PieceType::Knight, white, TileCoord::new(7, 3)),
+            ],
+            move_result.effects
+        );
+    }
+
+    #[test]
+    pub fn test_piece_ids() {
+        let mut board = Board::new();
+        let id = |board: &Board, coord: TileCoord| board.get_piece(&coord).unwrap().id();
+
+        // every piece has its own id
+        let mut ids: Vec<u32> = (0..64_usize)
+            .filter_map(|idx| board.get_piece(&idx.into()))
+            .map(|piece| piece.id())
+            .collect();
+        ids.dedup();
+        assert_eq!(32, ids.len());
+
+        // id is kept when moving
+        let knight_id = id(&board, TileCoord::new(0, 6));
+        board.move_piece(TileCoord::new(0, 6), TileCoord::new(2, 5), None);
+        assert_eq!(knight_id, id(&board, TileCoord::new(2, 5)));
+
+        // king and rook keep their ids when castling
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        let (king_id, rook_id) = (
+            id(&board, TileCoord::new(0, 4)),
+            id(&board, TileCoord::new(0, 7)),
+        );
+        board.move_piece(TileCoord::new(0, 4), TileCoord::new(0, 6), None);
+        assert_eq!(king_id, id(&board, TileCoord::new(0, 6)));
+        assert_eq!(rook_id, id(&board, TileCoord::new(0, 5)));
+
+        // promoted piece keeps the pawn id
+        let mut board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let pawn_id = id(&board, TileCoord::new(6, 0));
+        board.move_piece(
+            TileCoord::new(6, 0),
+            TileCoord::new(7, 0),
+            Some(PieceType::Queen),
+        );
+        assert_eq!(pawn_id, id(&board, TileCoord::new(7, 0)));
+    }
+
+    #[test]
+    pub fn test_tile_states_and_markers() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/5n2/R1b1K3 w - - 0 1").unwrap();
+        let tile = |board: &Board, coord: TileCoord| board.get_tile(&coord).unwrap().clone();
+        let rook = TileCoord::new(0, 0);
+
+        board.set_tile_state(&rook, TileState::Active).unwrap();
+        board.highlight_moves(rook);
+        assert_eq!(
+            TileState::Highlight,
+            tile(&board, TileCoord::new(0, 1)).state()
+        );
+        assert_eq!(
+            TileState::Capture,
+            tile(&board, TileCoord::new(0, 2)).state()
+        );
+
+        // king captures the knight, rook cannot reach it
+        board.highlight_moves(TileCoord::new(0, 4));
+        assert_eq!(
+            TileState::Capture,
+            tile(&board, TileCoord::new(1, 5)).state()
+        );
+
+        // states are cleared independently
+        board.clear_active_tiles();
+        assert_eq!(TileState::Inactive, tile(&board, rook).state());
+        assert!(tile(&board, TileCoord::new(0, 1)).is_highlighted());
+        board.clear_highlights();
+        assert!(!tile(&board, TileCoord::new(0, 1)).is_highlighted());
+
+        // hover is kept when the piece on the tile moves
+        board.set_hover(Some(TileCoord::new(7, 0)));
+        board.move_piece(rook, TileCoord::new(7, 0), None);
+        let to_tile = tile(&board, TileCoord::new(7, 0));
+        assert!(to_tile.markers().hover && to_tile.markers().last_move_to);
+        assert!(tile(&board, rook).markers().last_move_from);
+        assert!(tile(&board, TileCoord::new(7, 4)).markers().check);
+
+        board.move_piece(TileCoord::new(7, 4), TileCoord::new(6, 4), None);
+        assert!(!tile(&board, TileCoord::new(7, 4)).markers().check);
+        assert!(!tile(&board, TileCoord::new(7, 0)).markers().last_move_to);
+        board.clear_hover();
+        assert!(!tile(&board, TileCoord::new(7, 0)).markers().hover);
+    }
+
+    #[test]
+    pub fn test_tile_errors() {
+        let mut board = Board::new();
+
+        assert!(board.tile_by_index(63).is_ok());
+        assert_eq!(
+            Err(ChessError::TileIndexOutOfBounds(64)),
+            board.tile_by_index(64).map(|tile| tile.coord())
+        );
+
+        let empty = TileCoord::new(3, 3);
+        assert_eq!(
+            Err(ChessError::EmptyTile(empty)),
+            board.update_piece_state(&empty, PieceState::Selected)
+        );
+        assert!(board
+            .update_piece_state(&TileCoord::new(1, 3), PieceState::Selected)
+            .is_ok());
+    }
+}
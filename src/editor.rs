@@ -0,0 +1,468 @@
+use js_sys::Array;
+use std::fmt::Display;
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::error::{ChessError, ChessResult};
+use crate::game::Game;
+use crate::pieces::attack::AttackValidator;
+use crate::pieces::king::{KingCastleMoveResult, KingCastleState};
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::pieces::strategy::MoveValidator;
+use crate::tile::TileCoord;
+
+/// Reason an edited position cannot be played
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionIssueKind {
+    MissingKing,
+    TooManyKings,
+    TooManyPawns,
+    TooManyPieces,
+    /// pawn on the first or last rank
+    PawnOnBackRank,
+    /// side not to move is in check
+    InactiveKingInCheck,
+    /// castling rights without king or rook on its start coord
+    InvalidCastlingRights,
+    /// en passant pawn could not have just moved two tiles
+    InvalidEnPassant,
+}
+
+impl Display for PositionIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingKing => write!(f, "king is missing"),
+            Self::TooManyKings => write!(f, "there is more than one king"),
+            Self::TooManyPawns => write!(f, "there are too many pawns"),
+            Self::TooManyPieces => write!(f, "there are too many pieces"),
+            Self::PawnOnBackRank => write!(f, "pawns cannot be on the first or last rank"),
+            Self::InactiveKingInCheck => write!(f, "side not to move is in check"),
+            Self::InvalidCastlingRights => {
+                write!(f, "castling needs king and rook on their start tiles")
+            }
+            Self::InvalidEnPassant => write!(f, "en passant pawn cannot have just moved"),
+        }
+    }
+}
+
+/// Single issue found by `PositionValidator`,
+/// with the color and coord it applies to if any
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PositionIssue {
+    pub kind: PositionIssueKind,
+    pub piece_color: Option<PieceColor>,
+    pub coord: Option<TileCoord>,
+}
+
+#[wasm_bindgen]
+impl PositionIssue {
+    /// message explaining the issue, used by the board editor
+    pub fn message(&self) -> String {
+        self.kind.to_string()
+    }
+}
+
+impl PositionIssue {
+    fn new(kind: PositionIssueKind, piece_color: PieceColor, coord: Option<TileCoord>) -> Self {
+        Self {
+            kind,
+            piece_color: Some(piece_color),
+            coord,
+        }
+    }
+}
+
+pub struct PositionValidator {}
+
+impl PositionValidator {
+    /// all issues which make the position impossible to play,
+    /// piece counts are compared with the start position of the variant
+    pub fn validate(board: &Board) -> ChessResult<Vec<PositionIssue>> {
+        let mut issues = vec![];
+        let start_board = Board::variant_start(board.variant())?;
+
+        for piece_color in [PieceColor::White, PieceColor::Black] {
+            PositionValidator::validate_pieces(piece_color, board, &start_board, &mut issues);
+            PositionValidator::validate_castling(piece_color, board, &mut issues);
+        }
+
+        // side to move could take the king
+        let inactive_color = PieceColor::opposite_color(board.active_color());
+        if board.variant_rules().has_check() && MoveValidator::is_check(inactive_color, board) {
+            issues.push(PositionIssue::new(
+                PositionIssueKind::InactiveKingInCheck,
+                inactive_color,
+                AttackValidator::king_coord(inactive_color, board),
+            ));
+        }
+
+        if let Some(coord) = board.last_en_passant() {
+            if !PositionValidator::is_valid_en_passant(coord, board) {
+                issues.push(PositionIssue::new(
+                    PositionIssueKind::InvalidEnPassant,
+                    inactive_color,
+                    Some(coord),
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    fn validate_pieces(
+        piece_color: PieceColor,
+        board: &Board,
+        start_board: &Board,
+        issues: &mut Vec<PositionIssue>,
+    ) {
+        let coords = PositionValidator::piece_coords(piece_color, board);
+        let start_coords = PositionValidator::piece_coords(piece_color, start_board);
+
+        let count = |coords: &[(TileCoord, PieceType)], piece_type: PieceType| {
+            coords
+                .iter()
+                .filter(|(_, coord_piece_type)| *coord_piece_type == piece_type)
+                .count()
+        };
+
+        // kings are ordinary pieces in variants without check
+        if board.variant_rules().has_check() {
+            let kings = count(&coords, PieceType::King);
+            let start_kings = count(&start_coords, PieceType::King);
+
+            if kings < start_kings {
+                issues.push(PositionIssue::new(
+                    PositionIssueKind::MissingKing,
+                    piece_color,
+                    None,
+                ));
+            } else if kings > start_kings {
+                issues.push(PositionIssue::new(
+                    PositionIssueKind::TooManyKings,
+                    piece_color,
+                    None,
+                ));
+            }
+        }
+
+        if count(&coords, PieceType::Pawn) > count(&start_coords, PieceType::Pawn) {
+            issues.push(PositionIssue::new(
+                PositionIssueKind::TooManyPawns,
+                piece_color,
+                None,
+            ));
+        }
+
+        if coords.len() > start_coords.len() {
+            issues.push(PositionIssue::new(
+                PositionIssueKind::TooManyPieces,
+                piece_color,
+                None,
+            ));
+        }
+
+        // pawns may only start on the first rank if the variant does, ie. horde
+        let last_row = match piece_color {
+            PieceColor::White => 7,
+            PieceColor::Black => 0,
+        };
+        let first_row = 7 - last_row;
+        let has_first_row_pawns = start_coords
+            .iter()
+            .any(|(coord, piece_type)| *piece_type == PieceType::Pawn && coord.row() == first_row);
+
+        for (coord, piece_type) in coords {
+            if piece_type == PieceType::Pawn
+                && (coord.row() == last_row || (coord.row() == first_row && !has_first_row_pawns))
+            {
+                issues.push(PositionIssue::new(
+                    PositionIssueKind::PawnOnBackRank,
+                    piece_color,
+                    Some(coord),
+                ));
+            }
+        }
+    }
+
+    fn validate_castling(piece_color: PieceColor, board: &Board, issues: &mut Vec<PositionIssue>) {
+        if !board.variant_rules().has_castling() {
+            return;
+        }
+
+        let castle_state = board.king_castle_state();
+        let king_state = castle_state.king_state(piece_color);
+        if king_state.is_king_moved {
+            return;
+        }
+
+        let mut required = vec![];
+        if !king_state.a_file_rook_moved {
+            required.push((
+                PieceType::Rook,
+                castle_state.rook_start_coord(piece_color, &KingCastleMoveResult::LongCastle),
+            ));
+        }
+        if !king_state.h_file_rook_moved {
+            required.push((
+                PieceType::Rook,
+                castle_state.rook_start_coord(piece_color, &KingCastleMoveResult::ShortCastle),
+            ));
+        }
+        if required.is_empty() {
+            return;
+        }
+        required.push((PieceType::King, castle_state.king_start_coord(piece_color)));
+
+        for (piece_type, coord) in required {
+            let is_on_start = board.peek_tile(&coord).is_some_and(|piece| {
+                piece.piece_type() == piece_type && piece.color() == piece_color
+            });
+
+            if !is_on_start {
+                issues.push(PositionIssue::new(
+                    PositionIssueKind::InvalidCastlingRights,
+                    piece_color,
+                    Some(coord),
+                ));
+            }
+        }
+    }
+
+    /// en passant pawn belongs to the side not to move,
+    /// and the tiles it moved over are empty
+    fn is_valid_en_passant(coord: TileCoord, board: &Board) -> bool {
+        let pawn_color = PieceColor::opposite_color(board.active_color());
+        let (pawn_row, start_row, between_row) = match pawn_color {
+            PieceColor::White => (3, 1, 2),
+            PieceColor::Black => (4, 6, 5),
+        };
+
+        let is_pawn = board.peek_tile(&coord).is_some_and(|piece| {
+            piece.piece_type() == PieceType::Pawn && piece.color() == pawn_color
+        });
+
+        is_pawn
+            && coord.row() == pawn_row
+            && board
+                .peek_tile(&TileCoord::new(start_row, coord.col()))
+                .is_none()
+            && board
+                .peek_tile(&TileCoord::new(between_row, coord.col()))
+                .is_none()
+    }
+
+    fn piece_coords(piece_color: PieceColor, board: &Board) -> Vec<(TileCoord, PieceType)> {
+        (0..board.num_tiles())
+            .map(TileCoord::from)
+            .filter_map(|coord| {
+                board
+                    .peek_tile(&coord)
+                    .filter(|piece| piece.color() == piece_color)
+                    .map(|piece| (coord, piece.piece_type()))
+            })
+            .collect()
+    }
+}
+
+/// Free placement board used by the setup screen, pieces can be
+/// placed anywhere, the edited position is checked with `validate`
+/// before a game can be started from it
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct BoardEditor {
+    board: Board,
+}
+
+#[wasm_bindgen]
+impl BoardEditor {
+    /// start editing a copy of board
+    #[wasm_bindgen(constructor)]
+    pub fn new(board: &Board) -> Self {
+        Self {
+            board: board.clone(),
+        }
+    }
+
+    /// start editing a board without any pieces or castling rights
+    pub fn empty() -> Self {
+        let mut editor = Self {
+            board: Board::empty(),
+        };
+        editor.clear();
+        editor
+    }
+
+    /// copy of the edited board
+    pub fn board(&self) -> Board {
+        self.board.clone()
+    }
+
+    pub fn to_fen(&self) -> String {
+        self.board.to_fen()
+    }
+
+    /// place new piece on coord, replacing any piece on it
+    pub fn set_piece(
+        &mut self,
+        coord: TileCoord,
+        piece_type: PieceType,
+        piece_color: PieceColor,
+    ) -> Result<(), JsValue> {
+        if !coord.in_bounds() {
+            return Err(ChessError::CoordOutOfBounds(coord).into());
+        }
+        self.board
+            .set_new_tile(&coord, Some(piece_type), Some(piece_color));
+        Ok(())
+    }
+
+    pub fn clear_piece(&mut self, coord: TileCoord) -> Result<(), JsValue> {
+        if !coord.in_bounds() {
+            return Err(ChessError::CoordOutOfBounds(coord).into());
+        }
+        self.board.set_new_tile(&coord, None, None);
+        Ok(())
+    }
+
+    /// move piece without checking any rules, replacing any piece on to coord
+    pub fn move_piece(
+        &mut self,
+        from_coord: TileCoord,
+        to_coord: TileCoord,
+    ) -> Result<(), JsValue> {
+        if !to_coord.in_bounds() {
+            return Err(ChessError::CoordOutOfBounds(to_coord).into());
+        }
+        let piece = self
+            .board
+            .get_piece(&from_coord)
+            .ok_or(ChessError::EmptyTile(from_coord))?;
+
+        self.board.set_new_tile(&from_coord, None, None);
+        self.board.place_piece(&to_coord, piece);
+        Ok(())
+    }
+
+    /// remove all pieces and castling rights
+    pub fn clear(&mut self) {
+        let variant = self.board.variant();
+        self.board = Board::empty();
+        self.board.set_variant(variant);
+
+        for piece_color in [PieceColor::White, PieceColor::Black] {
+            self.set_castling(piece_color, false, false);
+        }
+    }
+
+    pub fn set_active_color(&mut self, piece_color: PieceColor) {
+        self.board.set_active_color(piece_color)
+    }
+
+    pub fn set_castling(&mut self, piece_color: PieceColor, can_short: bool, can_long: bool) {
+        let mut castle_state = self.board.king_castle_state();
+        *castle_state.king_state_mut(piece_color) =
+            KingCastleState::from_rights(can_short, can_long);
+        self.board.set_king_castle_state(castle_state);
+    }
+
+    /// coord of the pawn which can be taken en passant
+    pub fn set_en_passant(&mut self, coord: Option<TileCoord>) {
+        self.board.set_last_en_passant(coord)
+    }
+
+    /// JS array of `PositionIssue`, empty if a game can be started
+    pub fn js_validate(&self) -> Result<Array, JsValue> {
+        self.board.js_validate()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.board.validate().is_ok_and(|issues| issues.is_empty())
+    }
+
+    /// start new game from the edited position,
+    /// returns error if the position has any issues
+    pub fn start_game(&self) -> Result<Game, JsValue> {
+        Game::from_position(&self.board)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::editor::{BoardEditor, PositionIssueKind};
+    use crate::game::Game;
+    use crate::pieces::piece::{PieceColor, PieceType};
+    use crate::tile::TileCoord;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_validate_position() {
+        let kinds = |fen: &str| -> Vec<PositionIssueKind> {
+            Board::from_fen(fen)
+                .unwrap()
+                .validate()
+                .unwrap()
+                .iter()
+                .map(|issue| issue.kind)
+                .collect()
+        };
+
+        assert!(kinds("4k3/8/8/8/8/8/8/4K3 w - - 0 1").is_empty());
+        assert_eq!(
+            vec![PositionIssueKind::MissingKing],
+            kinds("8/8/8/8/8/8/8/4K3 w - - 0 1")
+        );
+        assert_eq!(
+            vec![PositionIssueKind::PawnOnBackRank],
+            kinds("4k2P/8/8/8/8/8/8/4K3 w - - 0 1")
+        );
+        // black to move could take the white king
+        assert_eq!(
+            vec![PositionIssueKind::InactiveKingInCheck],
+            kinds("4k3/8/8/8/8/8/8/r3K3 b - - 0 1")
+        );
+
+        // horde pawns may start on the first rank
+        assert!(Board::variant_start(Variant::Horde)
+            .unwrap()
+            .validate()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    pub fn test_board_editor() {
+        let mut editor = BoardEditor::empty();
+        editor
+            .set_piece(TileCoord::new(0, 4), PieceType::King, PieceColor::White)
+            .unwrap();
+        editor
+            .set_piece(TileCoord::new(0, 7), PieceType::Rook, PieceColor::White)
+            .unwrap();
+        editor
+            .set_piece(TileCoord::new(7, 3), PieceType::King, PieceColor::Black)
+            .unwrap();
+        editor
+            .move_piece(TileCoord::new(7, 3), TileCoord::new(7, 4))
+            .unwrap();
+
+        // long castle needs the a-file rook
+        editor.set_castling(PieceColor::White, true, true);
+        let issues = editor.board().validate().unwrap();
+        assert_eq!(1, issues.len());
+        assert_eq!(PositionIssueKind::InvalidCastlingRights, issues[0].kind);
+        assert_eq!(Some(TileCoord::new(0, 0)), issues[0].coord);
+
+        editor.set_castling(PieceColor::White, true, false);
+        assert!(editor.is_valid());
+
+        let game = Game::from_position(&editor.board()).unwrap();
+        assert_eq!("4k3/8/8/8/8/8/8/4K2R w K - 0 1", game.board().to_fen());
+    }
+}
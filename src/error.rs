@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use wasm_bindgen::prelude::*;
+
+use crate::notation::ParseError;
+use crate::tile::TileCoord;
+
+/// Crate wide error type
+/// converted into a JS `Error` when returned across
+/// the wasm-bindgen boundary so the frontend can catch it
+/// instead of the WASM instance aborting
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChessError {
+    /// value could not be serialized to bytes or JSON
+    Serialize(String),
+    /// bytes or JSON could not be deserialized into a value
+    Deserialize(String),
+    /// coord does not point to a tile on the board
+    CoordOutOfBounds(TileCoord),
+    /// tile at coord has no piece
+    EmptyTile(TileCoord),
+    /// tile index is not on the board
+    TileIndexOutOfBounds(usize),
+    /// no move recorded at index
+    MoveIndexOutOfBounds(usize),
+    /// no position recorded for ply
+    PlyOutOfBounds(usize),
+    /// move tree node does not exist or cannot be used
+    InvalidNode(usize),
+    /// FEN string could not be parsed
+    InvalidFen(String),
+    /// move could not be made on the board
+    IllegalMove(String),
+    /// chess960 start position index is not in 0..960
+    Chess960IndexOutOfBounds(u16),
+    /// edited position cannot be played, see `PositionValidator`
+    InvalidPosition(String),
+    /// square is not in algebraic notation, ie. "e4"
+    InvalidSquare(String),
+    /// move string is not in move notation, see `ParsedMove`
+    InvalidMoveStr(String, ParseError),
+}
+
+impl Display for ChessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialize(msg) => write!(f, "unable to serialize value: {msg}"),
+            Self::Deserialize(msg) => write!(f, "unable to deserialize value: {msg}"),
+            Self::CoordOutOfBounds(coord) => write!(f, "coord {coord} is not on the board"),
+            Self::EmptyTile(coord) => write!(f, "no piece on tile {coord}"),
+            Self::TileIndexOutOfBounds(index) => {
+                write!(f, "tile index {index} is not on the board")
+            }
+            Self::MoveIndexOutOfBounds(index) => write!(f, "no move at index {index}"),
+            Self::PlyOutOfBounds(ply) => write!(f, "no position at ply {ply}"),
+            Self::InvalidNode(id) => write!(f, "invalid move tree node {id}"),
+            Self::InvalidFen(msg) => write!(f, "invalid FEN: {msg}"),
+            Self::IllegalMove(move_str) => write!(f, "illegal move '{move_str}'"),
+            Self::Chess960IndexOutOfBounds(index) => {
+                write!(f, "chess960 start position {index} is not in 0..960")
+            }
+            Self::InvalidPosition(msg) => write!(f, "invalid position: {msg}"),
+            Self::InvalidSquare(square) => write!(f, "invalid square '{square}'"),
+            Self::InvalidMoveStr(move_str, err) => {
+                write!(f, "invalid move string '{move_str}': {err}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChessError {}
+
+impl From<ChessError> for JsValue {
+    fn from(err: ChessError) -> Self {
+        js_sys::Error::new(&err.to_string()).into()
+    }
+}
+
+impl From<bincode::Error> for ChessError {
+    fn from(err: bincode::Error) -> Self {
+        Self::Deserialize(err.to_string())
+    }
+}
+
+impl From<serde_wasm_bindgen::Error> for ChessError {
+    fn from(err: serde_wasm_bindgen::Error) -> Self {
+        Self::Deserialize(err.to_string())
+    }
+}
+
+/// result type used by crate methods that can fail
+pub type ChessResult<T> = Result<T, ChessError>;
@@ -0,0 +1,618 @@
+use crate::board::Board;
+use crate::error::{ChessError, ChessResult};
+use crate::pieces::king::{
+    CastleFiles, KingCastleBoardState, KingCastleMoveResult, KingCastleState, KingCastleValidator,
+};
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::tile::{TileCoord, TileFile, TileRank};
+use crate::variants::variant::Variant;
+
+/// Reads and writes boards in Forsyth-Edwards Notation
+pub struct Fen {}
+
+impl Fen {
+    /// write board to full FEN string
+    pub fn write(board: &Board) -> String {
+        format!(
+            "{} {} {}",
+            Fen::write_position(board),
+            board.halfmove_clock(),
+            board.fullmove_number()
+        )
+    }
+
+    /// write the first four FEN fields and any variant fields,
+    /// ie. without move clocks, used to compare positions
+    pub fn write_position(board: &Board) -> String {
+        let mut fields = vec![
+            Fen::write_placement(board),
+            Fen::color_char(board.active_color()).to_string(),
+            Fen::write_castling(board, false),
+            Fen::write_en_passant(board),
+        ];
+        fields.extend(board.variant_rules().write_fen_fields(board));
+
+        fields.join(" ")
+    }
+
+    /// write board to Shredder-FEN string
+    /// castling rights are always written as rook files, eg. HAha
+    pub fn write_shredder(board: &Board) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            Fen::write_placement(board),
+            Fen::color_char(board.active_color()),
+            Fen::write_castling(board, true),
+            Fen::write_en_passant(board),
+            board.halfmove_clock(),
+            board.fullmove_number()
+        )
+    }
+
+    /// parse FEN string into new board
+    /// move clocks are optional and default to "0 1"
+    pub fn parse(fen: &str) -> ChessResult<Board> {
+        Fen::parse_variant(fen, Variant::Standard)
+    }
+
+    /// parse FEN string into new variant board,
+    /// variant fields may follow the en passant field
+    pub fn parse_variant(fen: &str, variant: Variant) -> ChessResult<Board> {
+        let mut fields: Vec<&str> = fen.split_whitespace().collect();
+
+        let mut board = Board::empty();
+        board.set_variant(variant);
+
+        // remove variant fields so only move clocks follow en passant field
+        if fields.len() > 4 {
+            let variant_rules = board.variant_rules();
+            if variant_rules.parse_fen_field(fields[4], &mut board)? {
+                fields.remove(4);
+            }
+        }
+
+        if fields.len() < 4 || fields.len() > 6 {
+            return Err(ChessError::InvalidFen(format!(
+                "expected 4 to 6 fields, found {}",
+                fields.len()
+            )));
+        }
+
+        Fen::parse_placement(fields[0], &mut board)?;
+
+        let active_color = match fields[1] {
+            "w" => PieceColor::White,
+            "b" => PieceColor::Black,
+            other => {
+                return Err(ChessError::InvalidFen(format!(
+                    "invalid active color '{other}'"
+                )))
+            }
+        };
+        board.set_active_color(active_color);
+
+        let castle_state = Fen::parse_castling(fields[2], &board)?;
+        board.set_king_castle_state(castle_state);
+
+        let last_en_passant = Fen::parse_en_passant(fields[3], &board)?;
+        board.set_last_en_passant(last_en_passant);
+
+        let halfmove_clock = match fields.get(4) {
+            Some(field) => field
+                .parse::<u32>()
+                .map_err(|_| ChessError::InvalidFen(format!("invalid halfmove clock '{field}'")))?,
+            None => 0,
+        };
+
+        let fullmove_number = match fields.get(5) {
+            Some(field) => match field.parse::<u32>() {
+                Ok(num) if num > 0 => num,
+                _ => {
+                    return Err(ChessError::InvalidFen(format!(
+                        "invalid fullmove number '{field}'"
+                    )))
+                }
+            },
+            None => 1,
+        };
+        board.set_move_clocks(halfmove_clock, fullmove_number);
+
+        Ok(board)
+    }
+
+    pub fn piece_char(piece_type: PieceType, piece_color: PieceColor) -> char {
+        let piece_char = match piece_type {
+            PieceType::Pawn => 'p',
+            PieceType::Rook => 'r',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::King => 'k',
+            PieceType::Queen => 'q',
+        };
+
+        match piece_color {
+            PieceColor::White => piece_char.to_ascii_uppercase(),
+            PieceColor::Black => piece_char,
+        }
+    }
+
+    pub fn piece_from_char(piece_char: char) -> Option<(PieceType, PieceColor)> {
+        let piece_color = if piece_char.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+
+        let piece_type = match piece_char.to_ascii_lowercase() {
+            'p' => PieceType::Pawn,
+            'r' => PieceType::Rook,
+            'n' => PieceType::Knight,
+            'b' => PieceType::Bishop,
+            'k' => PieceType::King,
+            'q' => PieceType::Queen,
+            _ => return None,
+        };
+
+        Some((piece_type, piece_color))
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    fn color_char(piece_color: PieceColor) -> char {
+        match piece_color {
+            PieceColor::White => 'w',
+            PieceColor::Black => 'b',
+        }
+    }
+
+    fn write_placement(board: &Board) -> String {
+        let has_pockets = board.variant_rules().has_pockets();
+        let mut ranks = vec![];
+
+        // FEN starts from the 8th rank
+        for row in (0..8).rev() {
+            let mut rank_str = "".to_string();
+            let mut empty = 0;
+
+            for col in 0..8 {
+                match board.get_piece(&TileCoord::new(row, col)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            rank_str.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        rank_str.push(Fen::piece_char(piece.piece_type(), piece.color()));
+
+                        // promoted pieces are marked for drop variants
+                        if has_pockets && board.is_promoted(&TileCoord::new(row, col)) {
+                            rank_str.push('~');
+                        }
+                    }
+                    None => empty += 1,
+                }
+            }
+
+            if empty > 0 {
+                rank_str.push_str(&empty.to_string());
+            }
+
+            ranks.push(rank_str);
+        }
+
+        // pockets follow placement in drop variants, ie. "[Nq]"
+        if has_pockets {
+            let pockets: String = [PieceColor::White, PieceColor::Black]
+                .into_iter()
+                .flat_map(|piece_color| {
+                    board
+                        .pocket(piece_color)
+                        .pieces()
+                        .into_iter()
+                        .map(move |piece_type| Fen::piece_char(piece_type, piece_color))
+                })
+                .collect();
+
+            return format!("{}[{}]", ranks.join("/"), pockets);
+        }
+
+        ranks.join("/")
+    }
+
+    /// writes X-FEN castling rights, KQkq unless another rook is further
+    /// out than the castling rook, then the rook file is written
+    /// shredder flag always writes the rook file
+    fn write_castling(board: &Board, shredder: bool) -> String {
+        let castle_state = board.king_castle_state();
+        let mut castling = "".to_string();
+
+        if !board.variant_rules().has_castling() {
+            return "-".to_string();
+        }
+
+        for piece_color in [PieceColor::White, PieceColor::Black] {
+            let king = castle_state.king_state(piece_color);
+            if king.is_king_moved {
+                continue;
+            }
+
+            for (side, is_rook_moved, side_char) in [
+                (
+                    KingCastleMoveResult::ShortCastle,
+                    king.h_file_rook_moved,
+                    'k',
+                ),
+                (
+                    KingCastleMoveResult::LongCastle,
+                    king.a_file_rook_moved,
+                    'q',
+                ),
+            ] {
+                if is_rook_moved {
+                    continue;
+                }
+
+                let rook_coord = castle_state.rook_start_coord(piece_color, &side);
+                let castle_char = if shredder || !Fen::is_outer_rook(board, rook_coord, &side) {
+                    (b'a' + rook_coord.col()) as char
+                } else {
+                    side_char
+                };
+
+                castling.push(match piece_color {
+                    PieceColor::White => castle_char.to_ascii_uppercase(),
+                    PieceColor::Black => castle_char,
+                });
+            }
+        }
+
+        if castling.is_empty() {
+            "-".to_string()
+        } else {
+            castling
+        }
+    }
+
+    fn write_en_passant(board: &Board) -> String {
+        // board stores the coord of the pawn which moved two squares,
+        // FEN stores the square behind it
+        let Some(coord) = board.last_en_passant() else {
+            return "-".to_string();
+        };
+
+        match board.get_piece(&coord) {
+            Some(piece) if piece.piece_type() == PieceType::Pawn => match piece.color() {
+                PieceColor::White => TileCoord::new(coord.row() - 1, coord.col()).algebraic(),
+                PieceColor::Black => TileCoord::new(coord.row() + 1, coord.col()).algebraic(),
+            },
+            _ => "-".to_string(),
+        }
+    }
+
+    fn parse_placement(placement: &str, board: &mut Board) -> ChessResult<()> {
+        // split off pockets in drop variants, ie. "[Nq]"
+        let placement = match placement.split_once('[') {
+            Some((placement, pockets)) => {
+                let pockets = pockets
+                    .strip_suffix(']')
+                    .ok_or(ChessError::InvalidFen(format!(
+                        "invalid pockets '[{pockets}'"
+                    )))?;
+                Fen::parse_pockets(pockets, board)?;
+                placement
+            }
+            None => placement,
+        };
+
+        let ranks: Vec<&str> = placement.split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(ChessError::InvalidFen(format!(
+                "expected 8 ranks, found {}",
+                ranks.len()
+            )));
+        }
+
+        for (i, rank_str) in ranks.iter().enumerate() {
+            let row = 7 - i as u8;
+            let mut col: u8 = 0;
+            let too_many_files =
+                || ChessError::InvalidFen(format!("rank {} has more than 8 files", row + 1));
+
+            for piece_char in rank_str.chars() {
+                if let Some(empty) = piece_char.to_digit(10) {
+                    col = col
+                        .checked_add(empty as u8)
+                        .filter(|col| *col <= 8)
+                        .ok_or_else(too_many_files)?;
+                    continue;
+                }
+
+                // marks previous piece as promoted
+                if piece_char == '~' && col > 0 {
+                    let coord = TileCoord::new(row, col - 1);
+                    if let Some(piece) = board.get_piece(&coord) {
+                        board.promote_piece(&coord, piece.piece_type());
+                    }
+                    continue;
+                }
+
+                let (piece_type, piece_color) = Fen::piece_from_char(piece_char).ok_or(
+                    ChessError::InvalidFen(format!("invalid piece '{piece_char}'")),
+                )?;
+
+                if col > 7 {
+                    return Err(too_many_files());
+                }
+
+                board.set_new_tile(
+                    &TileCoord::new(row, col),
+                    Some(piece_type),
+                    Some(piece_color),
+                );
+                col += 1;
+            }
+
+            if col != 8 {
+                return Err(ChessError::InvalidFen(format!(
+                    "rank {} does not have 8 files",
+                    row + 1
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// parses crazyhouse pocket pieces, ie. "Nq" from "[Nq]"
+    fn parse_pockets(pockets: &str, board: &mut Board) -> ChessResult<()> {
+        for piece_char in pockets.chars() {
+            match Fen::piece_from_char(piece_char) {
+                Some((piece_type, piece_color)) if piece_type != PieceType::King => {
+                    board.add_pocket_piece(piece_type, piece_color)
+                }
+                _ => {
+                    return Err(ChessError::InvalidFen(format!(
+                        "invalid pocket piece '{piece_char}'"
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// parses KQkq, X-FEN and Shredder-FEN castling rights,
+    /// rook files other than a and h enable chess960 castling
+    fn parse_castling(castling: &str, board: &Board) -> ChessResult<KingCastleBoardState> {
+        // start with all castling lost, enable from field
+        let lost = KingCastleState {
+            is_king_moved: false,
+            is_in_check: false,
+            a_file_rook_moved: true,
+            h_file_rook_moved: true,
+        };
+        let mut castle_state = KingCastleBoardState::new();
+        castle_state.white_king = lost;
+        castle_state.black_king = lost;
+
+        if castling == "-" {
+            return Ok(castle_state);
+        }
+
+        // king, long rook and short rook cols found for each color,
+        // both colors share the same castle files
+        let mut white_cols: [Option<u8>; 3] = [None; 3];
+        let mut black_cols: [Option<u8>; 3] = [None; 3];
+
+        for castle_char in castling.chars() {
+            let invalid =
+                || ChessError::InvalidFen(format!("invalid castling right '{castle_char}'"));
+
+            let piece_color = if castle_char.is_ascii_uppercase() {
+                PieceColor::White
+            } else {
+                PieceColor::Black
+            };
+            let row = KingCastleValidator::back_row(piece_color);
+
+            let king_col = (0..8)
+                .find(|col| {
+                    Fen::is_piece_at(
+                        board,
+                        TileCoord::new(row, *col),
+                        PieceType::King,
+                        piece_color,
+                    )
+                })
+                .ok_or_else(invalid)?;
+
+            let is_rook = |col: &u8| {
+                Fen::is_piece_at(
+                    board,
+                    TileCoord::new(row, *col),
+                    PieceType::Rook,
+                    piece_color,
+                )
+            };
+
+            // find castling rook, outermost rook for K and Q
+            let rook_col = match castle_char.to_ascii_lowercase() {
+                'k' => (king_col + 1..8).rev().find(is_rook),
+                'q' => (0..king_col).find(is_rook),
+                file @ 'a'..='h' => Some(file as u8 - b'a').filter(is_rook),
+                _ => None,
+            }
+            .ok_or_else(invalid)?;
+
+            let cols = match piece_color {
+                PieceColor::White => &mut white_cols,
+                PieceColor::Black => &mut black_cols,
+            };
+            cols[0] = Some(king_col);
+
+            let king = castle_state.king_state_mut(piece_color);
+            if rook_col > king_col {
+                king.h_file_rook_moved = false;
+                cols[2] = Some(rook_col);
+            } else {
+                king.a_file_rook_moved = false;
+                cols[1] = Some(rook_col);
+            }
+        }
+
+        let default_files = CastleFiles::default();
+        let default_cols = [
+            default_files.king_col,
+            default_files.long_rook_col,
+            default_files.short_rook_col,
+        ];
+
+        let mut cols = default_cols;
+        for i in 0..3 {
+            cols[i] = match (white_cols[i], black_cols[i]) {
+                (Some(white_col), Some(black_col)) if white_col != black_col => {
+                    return Err(ChessError::InvalidFen(
+                        "castling king and rook files differ between colors".to_string(),
+                    ))
+                }
+                (white_col, black_col) => white_col.or(black_col).unwrap_or(default_cols[i]),
+            };
+        }
+
+        castle_state.files = CastleFiles::new(cols[0], cols[1], cols[2]);
+        castle_state.chess960 = castle_state.files != CastleFiles::default();
+
+        Ok(castle_state)
+    }
+
+    /// check no other own rook is between the rook and the board edge
+    fn is_outer_rook(board: &Board, rook_coord: TileCoord, side: &KingCastleMoveResult) -> bool {
+        let (row, col) = rook_coord.row_col();
+        let outer_cols = match side {
+            KingCastleMoveResult::ShortCastle => col + 1..8,
+            KingCastleMoveResult::LongCastle => 0..col,
+        };
+
+        let rook_color = match board.get_piece(&rook_coord) {
+            Some(piece) => piece.color(),
+            None => return true,
+        };
+
+        !outer_cols.into_iter().any(|col| {
+            Fen::is_piece_at(board, TileCoord::new(row, col), PieceType::Rook, rook_color)
+        })
+    }
+
+    fn is_piece_at(
+        board: &Board,
+        coord: TileCoord,
+        piece_type: PieceType,
+        piece_color: PieceColor,
+    ) -> bool {
+        match board.get_piece(&coord) {
+            Some(piece) => piece.piece_type() == piece_type && piece.color() == piece_color,
+            None => false,
+        }
+    }
+
+    fn parse_en_passant(en_passant: &str, board: &Board) -> ChessResult<Option<TileCoord>> {
+        if en_passant == "-" {
+            return Ok(None);
+        }
+
+        let invalid =
+            || ChessError::InvalidFen(format!("invalid en passant square '{en_passant}'"));
+
+        let mut chars = en_passant.chars();
+        let file: TileFile = chars.next().ok_or_else(invalid)?.into();
+        let rank: TileRank = chars.next().ok_or_else(invalid)?.into();
+
+        if chars.next().is_some() || file == TileFile::Unknown || rank == TileRank::Unknown {
+            return Err(invalid());
+        }
+
+        // convert square behind the pawn to pawn coord,
+        // pawn was moved by the player who is not active
+        let (row, col): (u8, u8) = (rank.into(), file.into());
+        let (pawn_row, pawn_color) = match board.active_color() {
+            PieceColor::White => (row as i8 - 1, PieceColor::Black),
+            PieceColor::Black => (row as i8 + 1, PieceColor::White),
+        };
+
+        if !(0..8).contains(&pawn_row) {
+            return Err(invalid());
+        }
+
+        let coord = TileCoord::new(pawn_row as u8, col);
+        if !Fen::is_piece_at(board, coord, PieceType::Pawn, pawn_color) {
+            return Err(invalid());
+        }
+
+        Ok(Some(coord))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::fen::Fen;
+    use crate::tile::TileCoord;
+
+    #[test]
+    pub fn test_fen_round_trip() {
+        assert_eq!(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Fen::write(&Board::new())
+        );
+
+        let fen = "r3k2r/pp3ppp/8/3pP3/8/8/PPP2PPP/R3K2R w Kq d6 0 12";
+        let board = Fen::parse(fen).unwrap();
+        assert_eq!(Some(TileCoord::new(4, 3)), board.last_en_passant());
+        assert_eq!(fen, Fen::write(&board));
+    }
+
+    #[test]
+    pub fn test_chess960_fen() {
+        // index 518 is the standard start position
+        assert_eq!(
+            Fen::write(&Board::new()),
+            Fen::write(&Board::new_chess960(518).unwrap())
+        );
+
+        let board = Board::new_chess960(0).unwrap();
+        assert_eq!(
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1",
+            Fen::write(&board)
+        );
+        assert_eq!(
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1",
+            Fen::write_shredder(&board)
+        );
+
+        // shredder castling rights are read back as chess960
+        let board = Fen::parse(&Fen::write_shredder(&board)).unwrap();
+        let castle_state = board.king_castle_state();
+        assert!(castle_state.chess960);
+        assert_eq!(6, castle_state.files.king_col);
+        assert_eq!(5, castle_state.files.long_rook_col);
+        assert_eq!(7, castle_state.files.short_rook_col);
+    }
+
+    #[test]
+    pub fn test_fen_invalid() {
+        assert!(Fen::parse("8/8/8 w - - 0 1").is_err());
+        assert!(Fen::parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1").is_err());
+        assert!(Fen::parse("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_err());
+        assert!(Fen::parse("rnbqkbnrp/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_err());
+        assert!(Fen::parse("8p/8/8/8/8/8/8/8 w - - 0 1").is_err());
+        assert!(Fen::parse("999999999999999999999999999999/8/8/8/8/8/8/8 w - - 0 1").is_err());
+        assert!(Fen::parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1").is_err());
+        assert!(Fen::parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e5 0 1").is_err());
+
+        // castling king file differs between colors
+        assert!(Fen::parse("rk5r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").is_err());
+        assert!(Fen::parse("rk5r/8/8/8/8/8/8/R3K2R w KQ - 0 1").is_ok());
+    }
+}
@@ -5,9 +5,19 @@ use wasm_bindgen::prelude::*;
 
 use crate::{
     board::Board,
-    console_log,
-    parser::{MoveParser, MoveReader},
-    pieces::piece::PieceColor,
+    error::{ChessError, ChessResult},
+    js::now_ms,
+    move_tree::MoveTree,
+    parser::{MoveReader, MoveResult, MoveWriter},
+    pieces::{
+        king::KingCastleMoveResult,
+        pawn::PawnMoveStrategy,
+        piece::{PieceColor, PieceType},
+        strategy::StrategyBuilder,
+    },
+    tile::{TileCoord, TileState},
+    variants::variant::Variant,
+    view::BoardView,
 };
 
 #[wasm_bindgen]
@@ -26,19 +36,75 @@ pub struct Game {
     online: bool,
     board: Board,
     winner: Option<PieceColor>,
+    /// board position after each ply
+    /// index 0 is the starting position
+    positions: Vec<Board>,
+    /// ply currently being viewed by the history cursor
+    view_ply: usize,
+    /// analysis tree, main line follows the moves played
+    tree: MoveTree,
+    /// tree node of the last move played
+    tree_tip: usize,
+    /// moves queued by the player not to move,
+    /// played in order once it is their turn
+    premoves: Vec<Premove>,
 }
 
 #[wasm_bindgen]
 impl Game {
     pub fn new() -> Self {
+        Game::from_board(Board::default())
+    }
+
+    /// create new game starting from FEN position
+    pub fn from_fen(fen: &str) -> Result<Game, JsValue> {
+        let board = Board::from_fen(fen)?;
+        Ok(Game::from_board(board))
+    }
+
+    /// create new game of variant from the default position
+    pub fn new_variant(variant: Variant) -> Result<Game, JsValue> {
+        let board = Board::new_variant(variant)?;
+        Ok(Game::from_board(board))
+    }
+
+    /// create new variant game starting from FEN position
+    pub fn from_variant_fen(fen: &str, variant: Variant) -> Result<Game, JsValue> {
+        let board = Board::from_variant_fen(fen, variant)?;
+        Ok(Game::from_board(board))
+    }
+
+    /// create new game from an edited position,
+    /// returns error if the position cannot be played, see `Board::validate`
+    pub fn from_position(board: &Board) -> Result<Game, JsValue> {
+        board.check_position()?;
+        Ok(Game::from_board(board.clone()))
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.board.variant()
+    }
+
+    /// create new chess960 game from start position index 0..960
+    pub fn new_chess960(index: u16) -> Result<Game, JsValue> {
+        let board = Board::new_chess960(index)?;
+        Ok(Game::from_board(board))
+    }
+
+    fn from_board(board: Board) -> Self {
         Self {
             state: GameState::Started,
-            player_turn: PieceColor::White,
+            player_turn: board.active_color(),
             moves: GameMoves::default(),
             online: false,
             player_color: PieceColor::White,
             winner: None,
-            board: Board::default(),
+            positions: vec![board.clone()],
+            view_ply: 0,
+            tree: MoveTree::from_start(board.fullmove_number() as usize, board.active_color()),
+            tree_tip: 0,
+            premoves: vec![],
+            board,
         }
     }
 
@@ -80,96 +146,245 @@ impl Game {
     }
 
     /// returns continuous string of moves
-    /// separated by ','
+    /// separated by ',', ie. "1.e2e4 e7e5,2.g1f3"
     pub fn print_moves(&self) -> String {
-        let GameMoves {
-            white_moves,
-            black_moves,
-        } = self.moves.moves();
+        self.moves.move_lines().join(",")
+    }
 
-        let mut moves_str = "".to_string();
+    pub fn moves(&self) -> GameMoves {
+        self.moves.moves()
+    }
 
-        for (i, _) in white_moves.iter().enumerate() {
-            let white_move = &white_moves[i];
-            let mut move_line_str = format!("{i}.{}", white_move.str());
+    /// records move and replays it on the game board,
+    /// the resulting position is stored for history navigation
+    pub fn add_move(&mut self, move_str: String, piece_color: PieceColor) -> Result<(), JsValue> {
+        self.record_move(move_str, piece_color)?;
+        Ok(())
+    }
 
-            if let Some(black_move) = black_moves.get(i) {
-                move_line_str.push_str(&format!(" {},", black_move.str()));
-            }
+    /// removes the last move and restores the previous position
+    pub fn undo_move(&mut self) -> Result<GameMove, JsValue> {
+        let game_move = self
+            .moves
+            .pop()
+            .ok_or(ChessError::MoveIndexOutOfBounds(0))?;
+
+        self.positions.pop();
+        self.board = self.positions[self.ply_count()].clone();
+        self.player_turn = self.board.active_color();
+        self.premoves.clear();
+
+        // game is no longer won after undoing the winning move
+        if self.winner.is_some() {
+            self.winner = None;
+            self.update_state(GameState::Started);
+        }
+        self.view_ply = self.view_ply.min(self.ply_count());
 
-            moves_str.push_str(&move_line_str)
+        // move tree keeps the move as a line for analysis
+        if let Ok(node) = self.tree.get_node(self.tree_tip) {
+            self.tree_tip = node.parent().unwrap_or(self.tree.root());
         }
 
-        moves_str
+        Ok(game_move)
     }
 
-    pub fn moves(&self) -> GameMoves {
-        self.moves.moves()
+    /// number of plies recorded
+    pub fn moves_len(&self) -> usize {
+        self.moves.len()
     }
 
-    pub fn add_move(&mut self, move_str: String, piece_color: PieceColor) {
-        self.moves.insert(move_str, piece_color)
+    /// returns move made at `ply`, first move of the game is ply 1
+    pub fn get_move(&self, ply: usize) -> Result<GameMove, JsValue> {
+        let game_move = self
+            .moves
+            .get(ply)
+            .ok_or(ChessError::MoveIndexOutOfBounds(ply))?;
+        Ok(game_move)
     }
 
-    pub fn moves_len(&self) -> usize {
-        self.moves.white_moves.len()
+    // ---
+    // history methods
+    // ---
+
+    /// number of plies played in the game
+    pub fn ply_count(&self) -> usize {
+        self.positions.len() - 1
+    }
+
+    /// returns clone of the board as it was after `ply` plies,
+    /// ply 0 is the starting position
+    pub fn position_at(&self, ply: usize) -> Result<Board, JsValue> {
+        let board = self
+            .positions
+            .get(ply)
+            .ok_or(ChessError::PlyOutOfBounds(ply))?;
+        Ok(board.clone())
     }
 
-    pub fn set_move(&mut self, index: usize, piece_color: PieceColor, new_move_str: &str) {
-        let new_move = GameMove::new(index, new_move_str);
-        match piece_color {
-            PieceColor::White => self.moves.white_moves[index] = new_move,
-            PieceColor::Black => self.moves.black_moves[index] = new_move,
+    /// ply currently viewed by the history cursor
+    pub fn view_ply(&self) -> usize {
+        self.view_ply
+    }
+
+    /// returns clone of the board at the history cursor
+    pub fn view_board(&self) -> Board {
+        self.positions[self.view_ply].clone()
+    }
+
+    /// true if the history cursor is on the live position
+    pub fn is_viewing_live(&self) -> bool {
+        self.view_ply == self.ply_count()
+    }
+
+    /// move history cursor to the starting position
+    pub fn first(&mut self) -> Board {
+        self.view_ply = 0;
+        self.view_board()
+    }
+
+    /// move history cursor back one ply,
+    /// stays on the starting position if already there
+    pub fn prev(&mut self) -> Board {
+        self.view_ply = self.view_ply.saturating_sub(1);
+        self.view_board()
+    }
+
+    /// move history cursor forward one ply,
+    /// stays on the live position if already there
+    pub fn next(&mut self) -> Board {
+        if !self.is_viewing_live() {
+            self.view_ply += 1;
         }
+        self.view_board()
+    }
+
+    /// move history cursor to the live position
+    pub fn last(&mut self) -> Board {
+        self.view_ply = self.ply_count();
+        self.view_board()
     }
 
-    pub fn get_move(&mut self, index: usize, piece_color: PieceColor) -> GameMove {
-        match piece_color {
-            PieceColor::White => self.moves.white_moves[index].clone(),
-            PieceColor::Black => self.moves.black_moves[index].clone(),
+    /// move history cursor to `ply`
+    pub fn goto(&mut self, ply: usize) -> Result<Board, JsValue> {
+        if ply > self.ply_count() {
+            return Err(ChessError::PlyOutOfBounds(ply).into());
         }
+
+        self.view_ply = ply;
+        Ok(self.view_board())
     }
 
     // ---
-    // static methods
+    // analysis methods
     // ---
 
-    pub fn from_string(all_moves_str: &str, move_index: usize) -> Game {
-        let mut board = Board::default();
+    /// returns clone of the analysis move tree
+    pub fn tree(&self) -> MoveTree {
+        self.tree.clone()
+    }
 
-        let (white_moves, black_moves) =
-            MoveReader::split_white_black_moves(all_moves_str.to_string());
+    /// replace the analysis move tree
+    /// used to store a tree edited in the frontend
+    pub fn set_tree(&mut self, tree: MoveTree) {
+        self.tree = tree
+    }
 
-        for (i, white_move) in white_moves.iter().enumerate() {
-            // make white move
-            let move_res = MoveParser::str_to_move_result(white_move, PieceColor::White);
+    /// returns board after replaying all moves
+    /// from the starting position up to tree node
+    pub fn position_at_node(&self, node_id: usize) -> Result<Board, JsValue> {
+        let mut board = self.positions[0].clone();
 
-            board.move_piece(move_res.from_coord, move_res.to_coord);
+        for id in self.tree.node_path(node_id)? {
+            let node = self.tree.get_node(id)?;
+            Game::apply_move_str(&mut board, &node.move_str(), node.piece_color())?;
+        }
 
-            // update board with promoted piece if is promote piece
-            if move_res.is_promote_piece {
-                board.set_new_tile(
-                    &move_res.to_coord,
-                    move_res.promote_piece_type,
-                    Some(move_res.piece_color),
-                )
-            }
+        Ok(board)
+    }
 
-            // make black move if exists
+    // ---
+    // premove methods
+    // ---
+
+    /// queue move for the player not to move,
+    /// move only has to be pseudo-legal for the piece in the position
+    /// after all queued premoves, it is validated when it is played
+    pub fn add_premove(
+        &mut self,
+        from_coord: TileCoord,
+        to_coord: TileCoord,
+        promote_piece_type: Option<PieceType>,
+    ) -> Result<(), JsValue> {
+        self.queue_premove(from_coord, to_coord, promote_piece_type)?;
+        Ok(())
+    }
+
+    /// returns JS array of all queued Premove in play order
+    pub fn premoves(&self) -> Array {
+        self.premoves
+            .iter()
+            .map(|premove| JsValue::from(*premove))
+            .collect()
+    }
+
+    pub fn premoves_len(&self) -> usize {
+        self.premoves.len()
+    }
+
+    /// cancel premove at index, later premoves
+    /// depend on it so they are cancelled as well
+    pub fn cancel_premove(&mut self, index: usize) -> Result<(), JsValue> {
+        if index >= self.premoves.len() {
+            return Err(ChessError::MoveIndexOutOfBounds(index).into());
         }
 
+        self.premoves.truncate(index);
+        Ok(())
+    }
+
+    pub fn clear_premoves(&mut self) {
+        self.premoves.clear()
+    }
+
+    /// set premove tile state on the from and to tiles
+    /// of all queued premoves
+    pub fn highlight_premoves(&self, board: &mut Board) -> Result<(), JsValue> {
+        for premove in &self.premoves {
+            for coord in [premove.from_coord, premove.to_coord] {
+                board.update_tile_state(&coord, TileState::Premove)?;
+            }
+        }
+        Ok(())
+    }
+
+    // ---
+    // static methods
+    // ---
+
+    /// create new game by replaying all moves in string,
+    /// history cursor is set to `move_index` ply
+    pub fn from_string(all_moves_str: &str, move_index: usize) -> Result<Game, JsValue> {
+        let mut game = Game::new();
+        game.play_moves_str(all_moves_str)?;
+
         // TODO:
         // update is online game
         // update piece_color
-        Self {
-            state: GameState::Started,
-            player_turn: PieceColor::White,
-            moves: GameMoves::default(),
-            online: false,
-            player_color: PieceColor::White,
-            winner: None,
-            board: Board::default(),
+        game.view_ply = move_index.min(game.ply_count());
+
+        Ok(game)
+    }
+
+    /// replay all moves in string starting with the player to move
+    pub fn play_moves_str(&mut self, all_moves_str: &str) -> Result<(), JsValue> {
+        for move_str in MoveReader::split_moves(all_moves_str) {
+            let piece_color = self.board.active_color();
+            self.add_move(move_str, piece_color)?;
+            self.set_player_turn(self.board.active_color());
         }
+
+        Ok(())
     }
 
     pub fn set_online(&mut self, is_online: bool) {
@@ -184,6 +399,17 @@ impl Game {
         self.player_color
     }
 
+    /// JS array of piece types taken by piece color,
+    /// ordered from queen to pawn, see `Material`
+    pub fn captured_pieces(&self, piece_color: PieceColor) -> Result<Array, JsValue> {
+        Ok(self.board.material()?.js_captured(piece_color))
+    }
+
+    /// board view with the player color at the bottom
+    pub fn board_view(&self) -> BoardView {
+        BoardView::new(self.player_color)
+    }
+
     pub fn is_online(&self) -> bool {
         self.online
     }
@@ -192,38 +418,217 @@ impl Game {
     // board proxy methods
     // ---
 
-    pub fn board() -> Board {
-        !todo!()
+    /// returns clone of the current game board
+    pub fn board(&self) -> Board {
+        self.board.clone()
     }
 
-    pub fn js_tiles() -> Array {
-        !todo!()
+    /// returns JS array of the current game board tiles
+    pub fn js_tiles(&self) -> Array {
+        self.board.js_tiles()
     }
+}
 
-    // pub fn board_state(&self, all_move_str: &str, move_index: usize) -> Board {
-    //     let board = Board::default();
+impl Game {
+    /// queue premove if it is pseudo-legal, see `add_premove`
+    fn queue_premove(
+        &mut self,
+        from_coord: TileCoord,
+        to_coord: TileCoord,
+        promote_piece_type: Option<PieceType>,
+    ) -> ChessResult<()> {
+        let piece_color = PieceColor::opposite_color(self.board.active_color());
+        let premove = Premove {
+            from_coord,
+            to_coord,
+            promote_piece_type,
+            piece_color,
+        };
 
-    //     let (white_moves, black_moves) = (self.moves.white_moves, self.moves.black_moves);
+        let board = self.premove_board();
+        let is_pseudo_legal = match board.get_piece(&from_coord) {
+            // pawns only move diagonally to take
+            Some(piece)
+                if piece.piece_type() == PieceType::Pawn
+                    && PawnMoveStrategy::diagonal_moves(piece_color, from_coord)
+                        .contains(&to_coord) =>
+            {
+                piece.color() == piece_color && board.get_piece(&to_coord).is_some()
+            }
+            Some(piece) if piece.color() == piece_color && from_coord != to_coord => {
+                StrategyBuilder::new_piece_strategy(
+                    piece.piece_type(),
+                    from_coord,
+                    piece_color,
+                    &board,
+                )
+                .moves()
+                .contains(&to_coord)
+            }
+            _ => false,
+        };
+
+        if !is_pseudo_legal {
+            return Err(ChessError::IllegalMove(premove.move_str()));
+        }
 
-    //     for (i, white_move) in white_moves.iter().enumerate() {
-    //         // make white move
-    //         let move_res = MoveParser::str_to_move_result(&white_move.str(), PieceColor::White);
-    //         board.move_piece(move_res.from_coord, move_res.to_coord);
+        self.premoves.push(premove);
+        Ok(())
+    }
+
+    /// replay move string on board
+    /// returns move result with check flags of the move made
+    fn apply_move_str(
+        board: &mut Board,
+        move_str: &str,
+        piece_color: PieceColor,
+    ) -> ChessResult<MoveResult> {
+        let mut parsed = MoveReader::default().parse_move(move_str, piece_color)?;
+
+        // only the side to move can move or drop a piece
+        if piece_color != board.active_color() {
+            return Err(ChessError::IllegalMove(move_str.to_string()));
+        }
 
-    //         // update board with promoted piece if is promote piece
-    //         if move_res.is_promote_piece {
-    //             board.set_new_tile(
-    //                 &move_res.to_coord,
-    //                 move_res.promote_piece_type,
-    //                 Some(move_res.piece_color),
-    //             )
-    //         }
+        // drop piece from pocket, ie. "N@f3"
+        if parsed.is_drop {
+            return board
+                .drop_piece(parsed.piece_type, piece_color, &parsed.to_coord)
+                .ok_or_else(|| ChessError::IllegalMove(move_str.to_string()));
+        }
 
-    //         // make black move if exists
-    //     }
+        // castle moves start from the kings start coord,
+        // chess960 castles are made by moving the king onto the rook
+        if parsed.is_short_castle || parsed.is_long_castle {
+            let castle_state = board.king_castle_state();
+            let side = if parsed.is_short_castle {
+                KingCastleMoveResult::ShortCastle
+            } else {
+                KingCastleMoveResult::LongCastle
+            };
+
+            parsed.from_coord = castle_state.king_start_coord(piece_color);
+            if castle_state.chess960 {
+                parsed.to_coord = castle_state.rook_start_coord(piece_color, &side);
+            }
+        }
 
-    //     board
-    // }
+        // pre move result includes check and checkmate flags
+        let illegal_move = || ChessError::IllegalMove(move_str.to_string());
+        let move_res = board
+            .pre_move_result(
+                parsed.from_coord,
+                parsed.to_coord,
+                parsed.promote_piece_type,
+            )
+            .ok_or_else(illegal_move)?;
+
+        // promotion without promote piece is only valid as a preview
+        board
+            .move_piece(
+                parsed.from_coord,
+                parsed.to_coord,
+                parsed.promote_piece_type,
+            )
+            .ok_or_else(illegal_move)?;
+
+        Ok(move_res)
+    }
+
+    /// records move and plays queued premove of the other player
+    fn record_move(&mut self, move_str: String, piece_color: PieceColor) -> ChessResult<()> {
+        // keep following the live position if it is currently viewed
+        let is_viewing_live = self.is_viewing_live();
+
+        let board_before = self.board.clone();
+        let move_result = Game::apply_move_str(&mut self.board, &move_str, piece_color)?;
+
+        let game_move = GameMove {
+            ply: self.moves.len() + 1,
+            move_number: board_before.fullmove_number() as usize,
+            san: MoveWriter::default().write_san(&move_result, &board_before),
+            move_str: move_str.clone(),
+            move_result,
+            fen: self.board.to_fen(),
+            hash: self.board.position_hash(),
+            timestamp: now_ms(),
+        };
+
+        self.positions.push(self.board.clone());
+        self.add_tree_move(&move_str, &game_move.san, piece_color);
+        self.moves.push(game_move);
+
+        if is_viewing_live {
+            self.view_ply = self.ply_count();
+        }
+
+        // variant win conditions, eg. king of the hill
+        if let Some(winner) = self.board.variant_winner() {
+            self.set_winner(winner);
+        }
+
+        self.play_premove();
+
+        Ok(())
+    }
+
+    /// play first queued premove if it is now the premove players turn,
+    /// all premoves are discarded if it is not legal
+    fn play_premove(&mut self) {
+        let premove = match self.premoves.first() {
+            Some(premove) if premove.piece_color == self.board.active_color() => *premove,
+            _ => return,
+        };
+
+        if self.winner.is_some() {
+            self.premoves.clear();
+            return;
+        }
+
+        self.premoves.remove(0);
+        if self
+            .record_move(premove.move_str(), premove.piece_color)
+            .is_err()
+        {
+            self.premoves.clear();
+            return;
+        }
+        self.set_player_turn(self.board.active_color());
+    }
+
+    /// board with all queued premove pieces moved,
+    /// used to check the next premove is pseudo-legal
+    fn premove_board(&self) -> Board {
+        let mut board = self.board.clone();
+
+        for premove in &self.premoves {
+            if let Some(piece) = board.get_piece(&premove.from_coord) {
+                // pieces keep their id, clearing a tile creates no piece
+                board.set_new_tile(&premove.from_coord, None, None);
+                board.place_piece(&premove.to_coord, piece);
+                if let Some(piece_type) = premove.promote_piece_type {
+                    board.promote_piece(&premove.to_coord, piece_type);
+                }
+            }
+        }
+
+        board
+    }
+
+    /// append played move to the analysis tree
+    fn add_tree_move(&mut self, move_str: &str, san: &str, piece_color: PieceColor) {
+        // tip may be gone if tree was replaced from the frontend
+        if self.tree.get_node(self.tree_tip).is_err() {
+            self.tree_tip = self.tree.mainline_tip();
+        }
+
+        if let Ok(id) = self
+            .tree
+            .insert_move(self.tree_tip, move_str, san, piece_color)
+        {
+            self.tree_tip = id;
+        }
+    }
 }
 
 impl Default for Game {
@@ -232,60 +637,125 @@ impl Default for Game {
     }
 }
 
+/// Move queued by the player not to move
+#[derive(Clone, Copy, Debug)]
+#[wasm_bindgen]
+pub struct Premove {
+    pub from_coord: TileCoord,
+    pub to_coord: TileCoord,
+    pub promote_piece_type: Option<PieceType>,
+    pub piece_color: PieceColor,
+}
+
+#[wasm_bindgen]
+impl Premove {
+    /// move string used to play the premove, ie. "e7e8=Q"
+    pub fn move_str(&self) -> String {
+        let move_str = format!(
+            "{}{}",
+            self.from_coord.algebraic(),
+            self.to_coord.algebraic()
+        );
+
+        match self.promote_piece_type {
+            Some(piece_type) => format!("{move_str}={piece_type}"),
+            None => move_str,
+        }
+    }
+}
+
+/// Single ply of the game with the position it resulted in
 #[derive(Clone)]
 #[wasm_bindgen]
 pub struct GameMove {
-    pub num: usize,
-    _str: String,
+    ply: usize,
+    move_number: usize,
+    move_str: String,
+    move_result: MoveResult,
+    san: String,
+    fen: String,
+    hash: u64,
+    timestamp: f64,
 }
 
 #[wasm_bindgen]
 impl GameMove {
-    pub fn new(num: usize, move_str: &str) -> Self {
-        Self {
-            num,
-            _str: move_str.to_string(),
-        }
+    /// ply of the move, first move of the game is ply 1
+    pub fn ply(&self) -> usize {
+        self.ply
     }
+
+    /// move number as written in notation, starting at 1
+    pub fn move_number(&self) -> usize {
+        self.move_number
+    }
+
+    pub fn piece_color(&self) -> PieceColor {
+        self.move_result.piece_color
+    }
+
     pub fn print(&self) -> String {
-        self._str.to_string()
+        self.to_string()
     }
 
+    /// move string as it was added to the game
     pub fn str(&self) -> String {
-        self._str.clone()
+        self.move_str.clone()
+    }
+
+    /// move in standard algebraic notation
+    pub fn san(&self) -> String {
+        self.san.clone()
+    }
+
+    pub fn move_result(&self) -> MoveResult {
+        self.move_result.clone()
+    }
+
+    /// FEN of the position after the move
+    pub fn fen(&self) -> String {
+        self.fen.clone()
+    }
+
+    /// hash of the position after the move
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// milliseconds since the unix epoch when the move was made
+    pub fn timestamp(&self) -> f64 {
+        self.timestamp
     }
 }
 
 impl Display for GameMove {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}", self.num, self._str)
+        match self.piece_color() {
+            PieceColor::White => write!(f, "{}.{}", self.move_number, self.move_str),
+            PieceColor::Black => write!(f, "{}...{}", self.move_number, self.move_str),
+        }
     }
 }
 
+/// All moves of the game in ply order
 #[derive(Clone)]
 #[wasm_bindgen]
 pub struct GameMoves {
-    white_moves: Vec<GameMove>,
-    black_moves: Vec<GameMove>,
+    moves: Vec<GameMove>,
 }
 
 #[wasm_bindgen]
 impl GameMoves {
     pub fn new() -> Self {
-        Self {
-            white_moves: vec![],
-            black_moves: vec![],
-        }
+        Self { moves: vec![] }
     }
 
-    pub fn insert(&mut self, move_str: String, piece_color: PieceColor) {
-        if piece_color == PieceColor::White {
-            let num = self.white_moves.len();
-            self.white_moves.push(GameMove::new(num, &move_str))
-        } else {
-            let num = self.black_moves.len();
-            self.black_moves.push(GameMove::new(num, &move_str))
-        }
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
     }
 
     pub fn moves(&self) -> GameMoves {
@@ -293,31 +763,22 @@ impl GameMoves {
     }
 
     /// returns JS format of string array
+    /// each line holds the move number and
+    /// the white and black move, ie. "1.e2e4 e7e5"
     /// used to return current game moves to
     /// frontend
     pub fn str_array(&self) -> Array {
-        let white_moves = &self.white_moves;
-        let black_moves = &self.black_moves;
-
-        let arr = Array::new_with_length(white_moves.len() as u32);
-
-        for (i, _) in white_moves.iter().enumerate() {
-            let white_move = &white_moves[i];
-            let mut move_line_str = format!("{i}.{}", white_move.str());
-
-            if let Some(black_move) = black_moves.get(i) {
-                move_line_str.push_str(&format!(" {}", black_move.str()));
-            }
-
-            arr.set(i as u32, JsValue::from_str(&move_line_str))
-        }
+        self.move_lines()
+            .iter()
+            .map(|line| JsValue::from_str(line))
+            .collect()
+    }
 
-        arr
+    /// returns JS array of all GameMove in ply order
+    pub fn js_moves(&self) -> Array {
+        self.moves.iter().cloned().map(JsValue::from).collect()
     }
-}
 
-#[wasm_bindgen]
-impl GameMoves {
     pub fn white_moves_js(&self) -> Array {
         self.js_array(PieceColor::White)
     }
@@ -327,29 +788,63 @@ impl GameMoves {
     }
 
     fn js_array(&self, color: PieceColor) -> Array {
-        let (len, moves) = match color {
-            PieceColor::White => (self.white_moves.len() as u32, &self.white_moves),
-            PieceColor::Black => (self.black_moves.len() as u32, &self.black_moves),
-        };
+        self.moves
+            .iter()
+            .filter(|game_move| game_move.piece_color() == color)
+            .map(|game_move| JsValue::from_str(&game_move.str()))
+            .collect()
+    }
+}
 
-        let arr = Array::new_with_length(len);
+impl GameMoves {
+    pub fn push(&mut self, game_move: GameMove) {
+        self.moves.push(game_move)
+    }
 
-        for (i, _) in moves.iter().enumerate() {
-            let move_str = &moves[i];
-            arr.set(i as u32, JsValue::from_str(&move_str.str()));
-        }
+    pub fn pop(&mut self) -> Option<GameMove> {
+        self.moves.pop()
+    }
 
-        arr
+    /// returns move at `ply`, first move is ply 1
+    pub fn get(&self, ply: usize) -> Option<GameMove> {
+        ply.checked_sub(1)
+            .and_then(|idx| self.moves.get(idx))
+            .cloned()
     }
-}
 
-impl GameMoves {
     pub fn white_moves(&self) -> Vec<GameMove> {
-        self.white_moves.clone()
+        self.color_moves(PieceColor::White)
     }
 
     pub fn black_moves(&self) -> Vec<GameMove> {
-        self.black_moves.clone()
+        self.color_moves(PieceColor::Black)
+    }
+
+    /// moves grouped by move number, ie. "1.e2e4 e7e5"
+    /// a black move without white move is written "1...e7e5"
+    pub fn move_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = vec![];
+
+        for game_move in &self.moves {
+            match game_move.piece_color() {
+                PieceColor::Black if game_move.ply > 1 => {
+                    if let Some(line) = lines.last_mut() {
+                        line.push_str(&format!(" {}", game_move.str()));
+                    }
+                }
+                _ => lines.push(game_move.to_string()),
+            }
+        }
+
+        lines
+    }
+
+    fn color_moves(&self, color: PieceColor) -> Vec<GameMove> {
+        self.moves
+            .iter()
+            .filter(|game_move| game_move.piece_color() == color)
+            .cloned()
+            .collect()
     }
 }
 
@@ -358,3 +853,192 @@ impl Default for GameMoves {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::game::Game;
+    use crate::pieces::piece::{PieceColor, PieceType};
+    use crate::tile::{TileCoord, TileState};
+
+    #[test]
+    pub fn test_chess960_castle() {
+        let fen = "6k1/8/8/8/8/8/8/RK5R w HA - 0 1";
+        let mut game = Game::from_fen(fen).unwrap();
+
+        // king moves onto rook, castling long
+        game.add_move("O-O-O".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!("6k1/8/8/8/8/8/8/2KR3R b - - 1 1", game.board().to_fen());
+        assert_eq!("O-O-O", game.get_move(1).unwrap().san());
+
+        let mut game = Game::from_fen(fen).unwrap();
+        game.add_move("b1h1".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!("6k1/8/8/8/8/8/8/R4RK1 b - - 1 1", game.board().to_fen());
+    }
+
+    #[test]
+    pub fn test_premoves() {
+        let mut game = Game::new();
+
+        // black queues moves while white is to move
+        game.add_premove(TileCoord::new(6, 4), TileCoord::new(4, 4), None)
+            .unwrap();
+        game.add_premove(TileCoord::new(7, 6), TileCoord::new(5, 5), None)
+            .unwrap();
+        assert_eq!(2, game.premoves_len());
+
+        // one premove is played after each white move
+        game.add_move("e2e4".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!(2, game.moves_len());
+        assert_eq!(1, game.premoves_len());
+
+        game.add_move("d2d4".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!(4, game.moves_len());
+        assert_eq!("Nf6", game.get_move(4).unwrap().san());
+
+        // premove is discarded once it is no longer legal
+        game.add_premove(TileCoord::new(4, 4), TileCoord::new(3, 4), None)
+            .unwrap();
+        game.add_move("d4e5".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!(5, game.moves_len());
+        assert_eq!(0, game.premoves_len());
+
+        // pawn premoves diagonally only onto a piece
+        assert!(game
+            .queue_premove(TileCoord::new(1, 0), TileCoord::new(2, 1), None)
+            .is_err());
+        game.queue_premove(TileCoord::new(4, 4), TileCoord::new(5, 5), None)
+            .unwrap();
+
+        let mut board = game.board();
+        game.highlight_premoves(&mut board).unwrap();
+        assert_eq!(TileState::Premove, board.tile_at_index(45).unwrap().state());
+    }
+
+    #[test]
+    pub fn test_promotion() {
+        let fen = "8/4P1k1/8/8/8/8/8/4K3 w - - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        let (from, to) = (TileCoord::new(6, 4), TileCoord::new(7, 4));
+
+        // promote piece must be chosen and valid
+        assert!(board.move_piece(from, to, None).is_none());
+        assert!(board.move_piece(from, to, Some(PieceType::King)).is_none());
+        assert!(board.move_piece(from, to, Some(PieceType::Pawn)).is_none());
+        assert!(
+            board
+                .pre_move_result(from, to, None)
+                .unwrap()
+                .is_promote_piece
+        );
+
+        // under promotion to knight gives check
+        let mut game = Game::from_fen(fen).unwrap();
+        game.add_move("e7e8=N".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!("e8=N+", game.get_move(1).unwrap().san());
+        assert_eq!("4N3/6k1/8/8/8/8/8/4K3 b - - 0 1", game.board().to_fen());
+    }
+
+    #[test]
+    pub fn test_history_cursor() {
+        let mut game = Game::from_string("1.e2e4 e7e5,2.g1f3", 3).unwrap();
+        assert_eq!(3, game.ply_count());
+        assert!(game.is_viewing_live());
+
+        // starting position untouched
+        let start = game.first();
+        assert!(start.get_piece(&TileCoord::new(1, 4)).is_some());
+        assert!(start.get_piece(&TileCoord::new(3, 4)).is_none());
+
+        // position after white first move
+        let board = game.next();
+        assert_eq!(1, game.view_ply());
+        let pawn = board.get_piece(&TileCoord::new(3, 4)).unwrap();
+        assert_eq!(PieceType::Pawn, pawn.piece_type());
+        assert!(board.get_piece(&TileCoord::new(4, 4)).is_none());
+
+        // new moves do not move cursor while browsing
+        game.add_move("b8c6".to_string(), PieceColor::Black)
+            .unwrap();
+        assert_eq!(1, game.view_ply());
+        assert_eq!(4, game.ply_count());
+
+        let board = game.last();
+        let knight = board.get_piece(&TileCoord::new(5, 2)).unwrap();
+        assert_eq!(PieceType::Knight, knight.piece_type());
+
+        // cursor stays in range
+        game.next();
+        assert_eq!(4, game.view_ply());
+        game.first();
+        game.prev();
+        assert_eq!(0, game.view_ply());
+    }
+
+    #[test]
+    pub fn test_tree_follows_moves() {
+        let mut game = Game::from_string("1.e2e4 e7e5", 0).unwrap();
+        let mut tree = game.tree();
+        let e4 = tree.mainline()[0];
+        let c5 = tree
+            .insert_move(e4, "c7c5", "c5", PieceColor::Black)
+            .unwrap();
+        game.set_tree(tree);
+
+        // played moves extend the main line, not the variation
+        game.add_move("g1f3".to_string(), PieceColor::White)
+            .unwrap();
+        let tree = game.tree();
+        assert_eq!(3, tree.mainline().len());
+        assert_eq!(c5, tree.get_node(e4).unwrap().children()[1]);
+        assert!(tree.get_node(c5).unwrap().children().is_empty());
+
+        let board = game.position_at_node(c5).unwrap();
+        assert!(board.get_piece(&TileCoord::new(4, 2)).is_some());
+        assert!(board.get_piece(&TileCoord::new(4, 4)).is_none());
+    }
+
+    #[test]
+    pub fn test_move_list() {
+        let mut game = Game::from_string("1.e2e4 e7e5,2.g1f3 b8c6,3.f1b5", 3).unwrap();
+        assert_eq!("1.e2e4 e7e5,2.g1f3 b8c6,3.f1b5", game.print_moves());
+
+        let game_move = game.get_move(3).unwrap();
+        assert_eq!(2, game_move.move_number());
+        assert_eq!("Nf3", game_move.san());
+        assert_eq!(
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2",
+            game_move.fen()
+        );
+        assert!(game.moves().get(0).is_none());
+
+        game.undo_move().unwrap();
+        assert_eq!(4, game.moves_len());
+        assert_eq!(PieceColor::White, game.player_turn());
+        assert_eq!(game.get_move(4).unwrap().fen(), game.board().to_fen());
+    }
+
+    #[test]
+    pub fn test_move_list_black_to_move() {
+        let mut game =
+            Game::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        assert_eq!(PieceColor::Black, game.player_turn());
+
+        game.play_moves_str("1...e7e5,2.g1f3").unwrap();
+        assert_eq!("1...e7e5,2.g1f3", game.print_moves());
+        assert_eq!(PieceColor::Black, game.player_turn());
+        assert_eq!("1... e5 2. Nf3", game.tree().pgn());
+
+        // white cannot move again while black is to move
+        assert!(game
+            .record_move("d2d4".to_string(), PieceColor::White)
+            .is_err());
+        assert_eq!(2, game.moves_len());
+    }
+}
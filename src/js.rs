@@ -7,3 +7,20 @@ extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     pub fn log(str: &str);
 }
+
+/// milliseconds since the unix epoch
+/// uses the JS clock when running in the browser
+pub fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as f64)
+            .unwrap_or(0.0)
+    }
+}
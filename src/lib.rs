@@ -4,10 +4,21 @@
 #![allow(clippy::too_many_arguments)]
 #![warn(clippy::wrong_self_convention)]
 mod board;
+mod editor;
+mod error;
+mod fen;
 mod game;
 mod js;
 mod macros;
+mod material;
+mod move_tree;
+mod notation;
 mod parser;
 mod pieces;
 mod player;
+mod selection;
+mod snapshot;
+mod square;
 mod tile;
+mod variants;
+mod view;
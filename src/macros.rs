@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! console_log {
     ($($t:tt)*) => {
-        use crate::js::log;
+        use $crate::js::log;
         (log(&format_args!($($t)*).to_string()))
     }
 
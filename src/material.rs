@@ -0,0 +1,178 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::variants::crazyhouse::Pocket;
+
+/// Captured pieces and material value of both colors
+///
+/// captured pieces are found by comparing the pieces on the board
+/// with the start position of the variant, pieces above the start
+/// count are treated as promoted pawns
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Material {
+    /// black pieces taken by white
+    white_captured: Vec<PieceType>,
+    /// white pieces taken by black
+    black_captured: Vec<PieceType>,
+    white_value: u32,
+    black_value: u32,
+}
+
+#[wasm_bindgen]
+impl Material {
+    pub fn new(board: &Board) -> Result<Material, JsValue> {
+        let start_board = Board::variant_start(board.variant())?;
+
+        Ok(Self {
+            white_captured: Material::lost_pieces(PieceColor::Black, board, &start_board),
+            black_captured: Material::lost_pieces(PieceColor::White, board, &start_board),
+            white_value: Material::board_value(PieceColor::White, board),
+            black_value: Material::board_value(PieceColor::Black, board),
+        })
+    }
+
+    /// JS array of piece types taken by piece color,
+    /// ordered from queen to pawn
+    pub fn js_captured(&self, piece_color: PieceColor) -> Array {
+        self.captured(piece_color)
+            .into_iter()
+            .map(JsValue::from)
+            .collect()
+    }
+
+    /// value of all pieces of piece color on the board,
+    /// pawn 1, knight and bishop 3, rook 5, queen 9
+    pub fn value(&self, piece_color: PieceColor) -> u32 {
+        match piece_color {
+            PieceColor::White => self.white_value,
+            PieceColor::Black => self.black_value,
+        }
+    }
+
+    /// material difference from the view of piece color,
+    /// positive if piece color is ahead
+    pub fn advantage(&self, piece_color: PieceColor) -> i32 {
+        let opposite_color = PieceColor::opposite_color(piece_color);
+        self.value(piece_color) as i32 - self.value(opposite_color) as i32
+    }
+
+    /// signed material score, positive if white is ahead
+    pub fn score(&self) -> i32 {
+        self.advantage(PieceColor::White)
+    }
+}
+
+impl Material {
+    /// piece types taken by piece color, ordered from queen to pawn
+    pub fn captured(&self, piece_color: PieceColor) -> Vec<PieceType> {
+        match piece_color {
+            PieceColor::White => self.white_captured.clone(),
+            PieceColor::Black => self.black_captured.clone(),
+        }
+    }
+
+    pub fn piece_value(piece_type: PieceType) -> u32 {
+        match piece_type {
+            PieceType::Pawn => 1,
+            PieceType::Knight | PieceType::Bishop => 3,
+            PieceType::Rook => 5,
+            PieceType::Queen => 9,
+            PieceType::King => 0,
+        }
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    /// pieces of piece color missing from the board compared to the start board
+    fn lost_pieces(piece_color: PieceColor, board: &Board, start_board: &Board) -> Vec<PieceType> {
+        let board_pocket = Material::board_pieces(piece_color, board);
+        let start_pocket = Material::board_pieces(piece_color, start_board);
+
+        // pieces above the start count were promoted from pawns
+        let promoted_count: u8 = Pocket::piece_types()
+            .into_iter()
+            .filter(|piece_type| *piece_type != PieceType::Pawn)
+            .map(|piece_type| {
+                board_pocket
+                    .count(piece_type)
+                    .saturating_sub(start_pocket.count(piece_type))
+            })
+            .sum();
+
+        Pocket::piece_types()
+            .into_iter()
+            .flat_map(|piece_type| {
+                let mut board_count = board_pocket.count(piece_type);
+                if piece_type == PieceType::Pawn {
+                    board_count += promoted_count;
+                }
+                let lost_count = start_pocket.count(piece_type).saturating_sub(board_count);
+                vec![piece_type; lost_count as usize]
+            })
+            .collect()
+    }
+
+    /// count of non king pieces of piece color on the board,
+    /// pieces promoted during the game are counted as pawns
+    fn board_pieces(piece_color: PieceColor, board: &Board) -> Pocket {
+        let mut pocket = Pocket::default();
+
+        for i in 0..board.num_tiles() {
+            let coord = i.into();
+            if let Some(piece) = board.peek_tile(&coord) {
+                if piece.color() != piece_color {
+                    continue;
+                }
+                if board.is_promoted(&coord) {
+                    pocket.add(PieceType::Pawn);
+                } else {
+                    pocket.add(piece.piece_type());
+                }
+            }
+        }
+
+        pocket
+    }
+
+    fn board_value(piece_color: PieceColor, board: &Board) -> u32 {
+        (0..board.num_tiles())
+            .filter_map(|i| board.peek_tile(&i.into()))
+            .filter(|piece| piece.color() == piece_color)
+            .map(|piece| Material::piece_value(piece.piece_type()))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::material::Material;
+    use crate::pieces::piece::{PieceColor, PieceType};
+
+    #[test]
+    pub fn test_material() {
+        let material = Board::new().material().unwrap();
+        assert!(material.captured(PieceColor::White).is_empty());
+        assert_eq!(39, material.value(PieceColor::White));
+        assert_eq!(0, material.score());
+
+        // white took a knight and a pawn, black took a rook,
+        // the extra white queen was promoted from a pawn
+        let fen = "rnbqkb1r/ppp1pppp/8/8/8/8/PPPPPPP1/RNBQKBNQ w Qkq - 0 1";
+        let material = Material::new(&Board::from_fen(fen).unwrap()).unwrap();
+        assert_eq!(
+            vec![PieceType::Knight, PieceType::Pawn],
+            material.captured(PieceColor::White)
+        );
+        assert_eq!(vec![PieceType::Rook], material.captured(PieceColor::Black));
+        assert_eq!(42, material.value(PieceColor::White));
+        assert_eq!(35, material.value(PieceColor::Black));
+        assert_eq!(7, material.score());
+        assert_eq!(-7, material.advantage(PieceColor::Black));
+    }
+}
@@ -0,0 +1,543 @@
+use js_sys::Array;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ChessError, ChessResult};
+use crate::pieces::piece::PieceColor;
+
+/// id of the root node, ie. the starting position
+/// root node has no move
+pub const ROOT_NODE_ID: usize = 0;
+
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MoveNode {
+    id: usize,
+    parent: Option<usize>,
+    /// first child is the main line continuation
+    /// all other children are variations
+    children: Vec<usize>,
+    move_str: String,
+    /// move in standard algebraic notation, written to PGN
+    san: String,
+    piece_color: PieceColor,
+    ply: usize,
+    /// fullmove number of the move, for the root node the
+    /// number of the last move before the starting position
+    move_number: usize,
+    comment: String,
+    nags: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl MoveNode {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    pub fn children(&self) -> Vec<usize> {
+        self.children.clone()
+    }
+
+    pub fn move_str(&self) -> String {
+        self.move_str.clone()
+    }
+
+    pub fn san(&self) -> String {
+        self.san.clone()
+    }
+
+    pub fn piece_color(&self) -> PieceColor {
+        self.piece_color
+    }
+
+    /// ply of the move, first move of the game is ply 1
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// move number as written in PGN,
+    /// counted from the fullmove number of the starting position
+    pub fn move_number(&self) -> usize {
+        self.move_number
+    }
+
+    pub fn comment(&self) -> String {
+        self.comment.clone()
+    }
+
+    /// numeric annotation glyphs, ie. $1 for '!'
+    pub fn nags(&self) -> Vec<u8> {
+        self.nags.clone()
+    }
+}
+
+/// Flattened node used by the frontend to render the tree
+/// nodes are returned in PGN order with the variation depth
+/// and the number of variations closed after the node
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct MoveTreeItem {
+    node: MoveNode,
+    pub depth: usize,
+    pub is_variation_start: bool,
+    pub variations_closed: usize,
+}
+
+#[wasm_bindgen]
+impl MoveTreeItem {
+    pub fn node(&self) -> MoveNode {
+        self.node.clone()
+    }
+}
+
+/// Tree of moves made up of the main line and
+/// nested variations, used for post game analysis
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct MoveTree {
+    /// deleted nodes are left as None so ids stay stable
+    nodes: Vec<Option<MoveNode>>,
+}
+
+#[wasm_bindgen]
+impl MoveTree {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        MoveTree::from_start(1, PieceColor::White)
+    }
+
+    /// tree for a game starting from a position with fullmove number
+    /// and active color, ie. from FEN
+    pub fn from_start(fullmove_number: usize, active_color: PieceColor) -> Self {
+        // root is the move before the starting position
+        let (piece_color, move_number) = match active_color {
+            PieceColor::White => (PieceColor::Black, fullmove_number.saturating_sub(1)),
+            PieceColor::Black => (PieceColor::White, fullmove_number),
+        };
+
+        let root = MoveNode {
+            id: ROOT_NODE_ID,
+            parent: None,
+            children: vec![],
+            move_str: "".to_string(),
+            san: "".to_string(),
+            piece_color,
+            ply: 0,
+            move_number,
+            comment: "".to_string(),
+            nags: vec![],
+        };
+
+        Self {
+            nodes: vec![Some(root)],
+        }
+    }
+
+    pub fn root(&self) -> usize {
+        ROOT_NODE_ID
+    }
+
+    pub fn node(&self, id: usize) -> Result<MoveNode, JsValue> {
+        Ok(self.get_node(id)?.clone())
+    }
+
+    /// add move after parent node, returns the id of the new node
+    /// if the move already exists after parent the existing node id is returned
+    /// the first move added after a node is the main line, others are variations,
+    /// san is the move in standard algebraic notation, see `MoveWriter::write_san`
+    pub fn add_move(
+        &mut self,
+        parent: usize,
+        move_str: &str,
+        san: &str,
+        piece_color: PieceColor,
+    ) -> Result<usize, JsValue> {
+        Ok(self.insert_move(parent, move_str, san, piece_color)?)
+    }
+
+    /// ids of all main line nodes, excluding the root
+    pub fn mainline(&self) -> Vec<usize> {
+        let mut ids = vec![];
+        let mut id = ROOT_NODE_ID;
+
+        while let Some(&child) = self.nodes[id].as_ref().and_then(|n| n.children.first()) {
+            ids.push(child);
+            id = child;
+        }
+
+        ids
+    }
+
+    /// id of the last node on the main line
+    pub fn mainline_tip(&self) -> usize {
+        self.mainline().last().copied().unwrap_or(ROOT_NODE_ID)
+    }
+
+    /// ids of nodes from the first move to node, excluding the root
+    pub fn path_to(&self, id: usize) -> Result<Vec<usize>, JsValue> {
+        Ok(self.node_path(id)?)
+    }
+
+    /// move variation containing node one place up in its parent's children,
+    /// a variation promoted to first place becomes the main line
+    pub fn promote_variation(&mut self, id: usize) -> Result<(), JsValue> {
+        // node already on the main line
+        let Some(start) = self.variation_start(id)? else {
+            return Ok(());
+        };
+
+        if let Some(parent) = self.get_node(start)?.parent {
+            let children = &mut self.get_node_mut(parent)?.children;
+            if let Some(pos) = children.iter().position(|&c| c == start) {
+                children.swap(pos, pos - 1);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// move node one place down in its parent's children,
+    /// demoting the main line makes the next variation the main line
+    pub fn demote_variation(&mut self, id: usize) -> Result<(), JsValue> {
+        if let Some(parent) = self.get_node(id)?.parent {
+            let children = &mut self.get_node_mut(parent)?.children;
+            if let Some(pos) = children.iter().position(|&c| c == id) {
+                if pos + 1 < children.len() {
+                    children.swap(pos, pos + 1);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// delete node and all moves after it
+    pub fn delete_variation(&mut self, id: usize) -> Result<(), JsValue> {
+        let parent = self
+            .get_node(id)?
+            .parent
+            .ok_or(ChessError::InvalidNode(id))?;
+
+        self.get_node_mut(parent)?.children.retain(|&c| c != id);
+
+        let mut to_delete = vec![id];
+        while let Some(next) = to_delete.pop() {
+            if let Some(node) = self.nodes[next].take() {
+                to_delete.extend(node.children);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn set_comment(&mut self, id: usize, comment: &str) -> Result<(), JsValue> {
+        self.get_node_mut(id)?.comment = comment.to_string();
+        Ok(())
+    }
+
+    pub fn add_nag(&mut self, id: usize, nag: u8) -> Result<(), JsValue> {
+        let node = self.get_node_mut(id)?;
+        if !node.nags.contains(&nag) {
+            node.nags.push(nag);
+        }
+        Ok(())
+    }
+
+    pub fn remove_nag(&mut self, id: usize, nag: u8) -> Result<(), JsValue> {
+        self.get_node_mut(id)?.nags.retain(|&n| n != nag);
+        Ok(())
+    }
+
+    /// returns node ids in PGN order, ie. each variation
+    /// follows the main line move it is an alternative to
+    pub fn pgn_order(&self) -> Vec<usize> {
+        self.items().iter().map(|item| item.node.id).collect()
+    }
+
+    /// returns JS array of MoveTreeItem in PGN order
+    /// used to render the tree in the frontend
+    pub fn js_items(&self) -> Array {
+        self.items().into_iter().map(JsValue::from).collect()
+    }
+
+    /// returns PGN movetext of the tree with variations,
+    /// comments and NAGs
+    pub fn pgn(&self) -> String {
+        let mut tokens = vec![];
+        if let Some(root) = &self.nodes[ROOT_NODE_ID] {
+            if !root.comment.is_empty() {
+                tokens.push(format!("{{{}}}", root.comment));
+            }
+        }
+        self.write_line(ROOT_NODE_ID, &mut tokens, true);
+        tokens.join(" ")
+    }
+}
+
+impl MoveTree {
+    pub fn get_node(&self, id: usize) -> ChessResult<&MoveNode> {
+        self.nodes
+            .get(id)
+            .and_then(|node| node.as_ref())
+            .ok_or(ChessError::InvalidNode(id))
+    }
+
+    fn get_node_mut(&mut self, id: usize) -> ChessResult<&mut MoveNode> {
+        self.nodes
+            .get_mut(id)
+            .and_then(|node| node.as_mut())
+            .ok_or(ChessError::InvalidNode(id))
+    }
+
+    pub fn insert_move(
+        &mut self,
+        parent: usize,
+        move_str: &str,
+        san: &str,
+        piece_color: PieceColor,
+    ) -> ChessResult<usize> {
+        let parent_node = self.get_node(parent)?;
+
+        // return existing node if move already played from parent
+        for &child in &parent_node.children {
+            if self.get_node(child)?.move_str == move_str {
+                return Ok(child);
+            }
+        }
+
+        let id = self.nodes.len();
+        let node = MoveNode {
+            id,
+            parent: Some(parent),
+            children: vec![],
+            move_str: move_str.to_string(),
+            san: san.to_string(),
+            piece_color,
+            ply: parent_node.ply + 1,
+            // new move number starts with white
+            move_number: match parent_node.piece_color {
+                PieceColor::White => parent_node.move_number,
+                PieceColor::Black => parent_node.move_number + 1,
+            },
+            comment: "".to_string(),
+            nags: vec![],
+        };
+
+        self.nodes.push(Some(node));
+        self.get_node_mut(parent)?.children.push(id);
+
+        Ok(id)
+    }
+
+    pub fn node_path(&self, id: usize) -> ChessResult<Vec<usize>> {
+        let mut path = vec![];
+        let mut node = self.get_node(id)?;
+
+        while let Some(parent) = node.parent {
+            path.push(node.id);
+            node = self.get_node(parent)?;
+        }
+
+        path.reverse();
+        Ok(path)
+    }
+
+    /// first node of the variation containing id,
+    /// ie. the closest ancestor which is not a main line child
+    /// returns None if node is on the main line
+    fn variation_start(&self, id: usize) -> ChessResult<Option<usize>> {
+        let mut node = self.get_node(id)?;
+
+        while let Some(parent) = node.parent {
+            let parent_node = self.get_node(parent)?;
+            if parent_node.children.first() != Some(&node.id) {
+                return Ok(Some(node.id));
+            }
+            node = parent_node;
+        }
+
+        Ok(None)
+    }
+
+    fn items(&self) -> Vec<MoveTreeItem> {
+        let mut items = vec![];
+        self.collect_items(ROOT_NODE_ID, 0, &mut items);
+        items
+    }
+
+    /// walk line starting after node, pushing items in PGN order
+    fn collect_items(&self, id: usize, depth: usize, items: &mut Vec<MoveTreeItem>) {
+        let mut cur = id;
+
+        while let Some(node) = &self.nodes[cur] {
+            let Some((&main, variations)) = node.children.split_first() else {
+                break;
+            };
+
+            if let Some(main_node) = &self.nodes[main] {
+                items.push(MoveTreeItem {
+                    node: main_node.clone(),
+                    depth,
+                    is_variation_start: false,
+                    variations_closed: 0,
+                });
+            }
+
+            // variations are written after the main line move
+            for &variation in variations {
+                if let Some(var_node) = &self.nodes[variation] {
+                    items.push(MoveTreeItem {
+                        node: var_node.clone(),
+                        depth: depth + 1,
+                        is_variation_start: true,
+                        variations_closed: 0,
+                    });
+                    self.collect_items(variation, depth + 1, items);
+
+                    if let Some(last) = items.last_mut() {
+                        last.variations_closed += 1;
+                    }
+                }
+            }
+
+            cur = main;
+        }
+    }
+
+    /// write PGN tokens for line starting after node
+    fn write_line(&self, id: usize, tokens: &mut Vec<String>, mut force_number: bool) {
+        let mut cur = id;
+
+        while let Some(node) = &self.nodes[cur] {
+            let Some((&main, variations)) = node.children.split_first() else {
+                break;
+            };
+
+            if let Some(main_node) = &self.nodes[main] {
+                tokens.push(MoveTree::move_token(main_node, force_number));
+                force_number = !main_node.comment.is_empty() || !variations.is_empty();
+            }
+
+            for &variation in variations {
+                if let Some(var_node) = &self.nodes[variation] {
+                    let mut var_tokens = vec![MoveTree::move_token(var_node, true)];
+                    self.write_line(variation, &mut var_tokens, !var_node.comment.is_empty());
+                    tokens.push(format!("({})", var_tokens.join(" ")));
+                }
+            }
+
+            cur = main;
+        }
+    }
+
+    /// write single move with number, NAGs and comment
+    fn move_token(node: &MoveNode, force_number: bool) -> String {
+        let mut token = match node.piece_color {
+            PieceColor::White => format!("{}. {}", node.move_number(), node.san),
+            PieceColor::Black if force_number => {
+                format!("{}... {}", node.move_number(), node.san)
+            }
+            PieceColor::Black => node.san.clone(),
+        };
+
+        for nag in &node.nags {
+            token.push_str(&format!(" ${nag}"));
+        }
+
+        if !node.comment.is_empty() {
+            token.push_str(&format!(" {{{}}}", node.comment));
+        }
+
+        token
+    }
+}
+
+impl Default for MoveTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::move_tree::{MoveTree, ROOT_NODE_ID};
+    use crate::pieces::piece::PieceColor;
+
+    fn tree() -> (MoveTree, Vec<usize>) {
+        let mut tree = MoveTree::new();
+        let e4 = tree
+            .insert_move(ROOT_NODE_ID, "e2e4", "e4", PieceColor::White)
+            .unwrap();
+        let e5 = tree
+            .insert_move(e4, "e7e5", "e5", PieceColor::Black)
+            .unwrap();
+        let nf3 = tree
+            .insert_move(e5, "g1f3", "Nf3", PieceColor::White)
+            .unwrap();
+        let c5 = tree
+            .insert_move(e4, "c7c5", "c5", PieceColor::Black)
+            .unwrap();
+        let c5_nf3 = tree
+            .insert_move(c5, "g1f3", "Nf3", PieceColor::White)
+            .unwrap();
+        (tree, vec![e4, e5, nf3, c5, c5_nf3])
+    }
+
+    #[test]
+    pub fn test_tree_pgn_order() {
+        let (tree, ids) = tree();
+        assert_eq!(vec![ids[0], ids[1], ids[2]], tree.mainline());
+        assert_eq!(
+            vec![ids[0], ids[1], ids[3], ids[4], ids[2]],
+            tree.pgn_order()
+        );
+        assert_eq!("1. e4 e5 (1... c5 2. Nf3) 2. Nf3", tree.pgn());
+
+        // existing moves are not duplicated
+        let mut tree = tree;
+        assert_eq!(
+            ids[1],
+            tree.insert_move(ids[0], "e7e5", "e5", PieceColor::Black)
+                .unwrap()
+        );
+
+        // numbers follow the starting position, ie. from FEN
+        let mut tree = MoveTree::from_start(5, PieceColor::Black);
+        let e5 = tree
+            .insert_move(ROOT_NODE_ID, "e7e5", "e5", PieceColor::Black)
+            .unwrap();
+        tree.insert_move(e5, "g1f3", "Nf3", PieceColor::White)
+            .unwrap();
+        assert_eq!("5... e5 6. Nf3", tree.pgn());
+    }
+
+    #[test]
+    pub fn test_tree_promote_delete() {
+        let (mut tree, ids) = tree();
+        tree.promote_variation(ids[4]).unwrap();
+        assert_eq!(vec![ids[0], ids[3], ids[4]], tree.mainline());
+
+        tree.demote_variation(ids[3]).unwrap();
+        assert_eq!(vec![ids[0], ids[1], ids[2]], tree.mainline());
+
+        tree.delete_variation(ids[3]).unwrap();
+        assert!(tree.get_node(ids[4]).is_err());
+        assert_eq!(vec![ids[0], ids[1], ids[2]], tree.pgn_order());
+    }
+
+    #[test]
+    pub fn test_tree_annotations() {
+        let (mut tree, ids) = tree();
+        tree.add_nag(ids[0], 1).unwrap();
+        tree.set_comment(ids[1], "main line").unwrap();
+        assert_eq!(
+            "1. e4 $1 e5 {main line} (1... c5 2. Nf3) 2. Nf3",
+            tree.pgn()
+        );
+    }
+}
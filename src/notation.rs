@@ -0,0 +1,507 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::pieces::king::{KingCastleMoveResult, KingCastleValidator};
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::square::Square;
+use crate::tile::TileCoord;
+
+/// Annotation glyph written after a move, ie. "e2e4!?"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveGlyph {
+    /// "!"
+    Good,
+    /// "?"
+    Mistake,
+    /// "!!"
+    Brilliant,
+    /// "??"
+    Blunder,
+    /// "!?"
+    Interesting,
+    /// "?!"
+    Dubious,
+}
+
+impl MoveGlyph {
+    fn from_glyph_str(glyph_str: &str) -> Option<MoveGlyph> {
+        match glyph_str {
+            "!" => Some(Self::Good),
+            "?" => Some(Self::Mistake),
+            "!!" => Some(Self::Brilliant),
+            "??" => Some(Self::Blunder),
+            "!?" => Some(Self::Interesting),
+            "?!" => Some(Self::Dubious),
+            _ => None,
+        }
+    }
+}
+
+impl Display for MoveGlyph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let glyph_str = match self {
+            Self::Good => "!",
+            Self::Mistake => "?",
+            Self::Brilliant => "!!",
+            Self::Blunder => "??",
+            Self::Interesting => "!?",
+            Self::Dubious => "?!",
+        };
+        write!(f, "{glyph_str}")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// move string has no moves
+    Empty,
+    /// char is not part of move notation or not allowed here
+    UnexpectedChar(char),
+    /// file is not followed by a rank, ie. "e9"
+    InvalidSquare,
+    /// move string ended or continued before a square
+    ExpectedSquare,
+    /// promote piece is a pawn or the moving piece is not a pawn
+    InvalidPromotion,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty move"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected '{c}'"),
+            Self::InvalidSquare => write!(f, "invalid square"),
+            Self::ExpectedSquare => write!(f, "expected square"),
+            Self::InvalidPromotion => write!(f, "invalid promotion"),
+        }
+    }
+}
+
+/// Error parsing a move string,
+/// position is the index of the char the error was found at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    fn new(position: usize, kind: ParseErrorKind) -> Self {
+        Self { position, kind }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Move read from a move string, only the notation is checked,
+/// the move may still be illegal on the board
+///
+/// accepted notation, see `MoveWriter::write_move`
+/// - moves "e2e4", "Ng1f3", "Bf1xb5", "e2-e4", "e7e8=Q"
+/// - castles "0-0", "O-O", "0-0-0", "O-O-O"
+/// - drops "N@f3", "@e4", "P@e4"
+/// - optional "+" or "#" then a glyph "!", "?", "!!", "??", "!?", "?!"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParsedMove {
+    pub piece_type: PieceType,
+    /// None for castles and drops
+    pub from_square: Option<Square>,
+    /// None for castles
+    pub to_square: Option<Square>,
+    pub promote_piece_type: Option<PieceType>,
+    pub castle: Option<KingCastleMoveResult>,
+    pub is_take: bool,
+    pub is_drop: bool,
+    pub is_check: bool,
+    pub is_checkmate: bool,
+    pub glyph: Option<MoveGlyph>,
+}
+
+impl ParsedMove {
+    /// from and to coord of the move for piece color,
+    /// castles use the standard king start and castle coords
+    pub fn coords(&self, piece_color: PieceColor) -> (TileCoord, TileCoord) {
+        match (self.castle, self.from_square, self.to_square) {
+            (Some(KingCastleMoveResult::ShortCastle), _, _) => (
+                KingCastleValidator::king_start_coord(piece_color),
+                KingCastleValidator::short_castle_coord(piece_color),
+            ),
+            (Some(KingCastleMoveResult::LongCastle), _, _) => (
+                KingCastleValidator::king_start_coord(piece_color),
+                KingCastleValidator::long_castle_coord(piece_color),
+            ),
+            (None, Some(from_square), Some(to_square)) => (from_square.into(), to_square.into()),
+            // dropped piece has no from coord, use to coord
+            (None, None, Some(to_square)) => (to_square.into(), to_square.into()),
+            // SAFETY: parser always sets the to square of non castle moves
+            (None, _, None) => unreachable!("parsed move without to square"),
+        }
+    }
+
+    fn new(piece_type: PieceType) -> Self {
+        Self {
+            piece_type,
+            from_square: None,
+            to_square: None,
+            promote_piece_type: None,
+            castle: None,
+            is_take: false,
+            is_drop: false,
+            is_check: false,
+            is_checkmate: false,
+            glyph: None,
+        }
+    }
+}
+
+impl FromStr for ParsedMove {
+    type Err = ParseError;
+
+    fn from_str(move_str: &str) -> Result<Self, Self::Err> {
+        let tokens = Tokenizer::new(move_str).tokenize()?;
+        MoveTokenParser::new(tokens, move_str).parse()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    Piece(PieceType),
+    Square(Square),
+    Castle(KingCastleMoveResult),
+    /// "x"
+    Take,
+    /// "-" between from and to square
+    Dash,
+    /// "="
+    Promote,
+    /// "@"
+    Drop,
+    /// "+"
+    Check,
+    /// "#"
+    Checkmate,
+    Glyph(MoveGlyph),
+}
+
+/// Splits a move string into tokens with the position of their first char
+struct Tokenizer {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Tokenizer {
+    fn new(move_str: &str) -> Self {
+        Self {
+            chars: move_str.chars().collect(),
+            position: 0,
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<(usize, Token)>, ParseError> {
+        let mut tokens = vec![];
+
+        while self.position < self.chars.len() {
+            let start = self.position;
+            let token = self.next_token()?;
+            tokens.push((start, token));
+        }
+
+        Ok(tokens)
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        if let Some(castle) = self.castle() {
+            return Ok(Token::Castle(castle));
+        }
+
+        let position = self.position;
+        let c = self.chars[position];
+        self.position += 1;
+
+        let token = match c {
+            'K' => Token::Piece(PieceType::King),
+            'Q' => Token::Piece(PieceType::Queen),
+            'R' => Token::Piece(PieceType::Rook),
+            'B' => Token::Piece(PieceType::Bishop),
+            'N' => Token::Piece(PieceType::Knight),
+            'P' => Token::Piece(PieceType::Pawn),
+            'a'..='h' => {
+                let square = self
+                    .chars
+                    .get(position..position + 2)
+                    .and_then(|square_chars| square_chars.iter().collect::<String>().parse().ok())
+                    .ok_or(ParseError::new(position, ParseErrorKind::InvalidSquare))?;
+                self.position += 1;
+                Token::Square(square)
+            }
+            'x' => Token::Take,
+            '-' => Token::Dash,
+            '=' => Token::Promote,
+            '@' => Token::Drop,
+            '+' => Token::Check,
+            '#' => Token::Checkmate,
+            '!' | '?' => {
+                // glyphs are at most two chars, ie. "!?"
+                let end = (position + 2).min(self.chars.len());
+                let two_chars: String = self.chars[position..end].iter().collect();
+
+                if let Some(glyph) = MoveGlyph::from_glyph_str(&two_chars) {
+                    self.position = end;
+                    Token::Glyph(glyph)
+                } else {
+                    // SAFETY: single '!' or '?' is always a glyph
+                    Token::Glyph(MoveGlyph::from_glyph_str(&c.to_string()).unwrap())
+                }
+            }
+            _ => return Err(ParseError::new(position, ParseErrorKind::UnexpectedChar(c))),
+        };
+
+        Ok(token)
+    }
+
+    /// castle written with zeros or letter O, ie. "0-0-0" or "O-O"
+    fn castle(&mut self) -> Option<KingCastleMoveResult> {
+        let castle_char = *self.chars.get(self.position)?;
+        if castle_char != '0' && castle_char != 'O' {
+            return None;
+        }
+
+        let long_castle = [castle_char, '-', castle_char, '-', castle_char];
+        let short_castle = &long_castle[..3];
+        let rest = &self.chars[self.position..];
+
+        if rest.starts_with(&long_castle) {
+            self.position += long_castle.len();
+            Some(KingCastleMoveResult::LongCastle)
+        } else if rest.starts_with(short_castle) {
+            self.position += short_castle.len();
+            Some(KingCastleMoveResult::ShortCastle)
+        } else {
+            None
+        }
+    }
+}
+
+/// Builds a `ParsedMove` from tokens, see `ParsedMove` for the grammar
+struct MoveTokenParser {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+    /// chars of the move string, used to report unexpected tokens
+    chars: Vec<char>,
+}
+
+impl MoveTokenParser {
+    fn new(tokens: Vec<(usize, Token)>, move_str: &str) -> Self {
+        Self {
+            tokens,
+            index: 0,
+            chars: move_str.chars().collect(),
+        }
+    }
+
+    fn parse(mut self) -> Result<ParsedMove, ParseError> {
+        let mut parsed = match self.peek() {
+            None => return Err(ParseError::new(0, ParseErrorKind::Empty)),
+            Some(Token::Castle(castle)) => {
+                self.index += 1;
+                ParsedMove {
+                    castle: Some(castle),
+                    ..ParsedMove::new(PieceType::King)
+                }
+            }
+            Some(_) => self.piece_move()?,
+        };
+
+        if self.next_if(Token::Checkmate) {
+            parsed.is_checkmate = true;
+        } else if self.next_if(Token::Check) {
+            parsed.is_check = true;
+        }
+
+        if let Some(Token::Glyph(glyph)) = self.peek() {
+            self.index += 1;
+            parsed.glyph = Some(glyph);
+        }
+
+        // nothing is allowed after the glyph
+        if let Some(&(position, _)) = self.tokens.get(self.index) {
+            return Err(self.unexpected_char(position));
+        }
+
+        Ok(parsed)
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    /// move or drop, with optional piece letter
+    fn piece_move(&mut self) -> Result<ParsedMove, ParseError> {
+        let piece_type = match self.peek() {
+            Some(Token::Piece(piece_type)) => {
+                self.index += 1;
+                piece_type
+            }
+            _ => PieceType::Pawn,
+        };
+        let mut parsed = ParsedMove::new(piece_type);
+
+        if self.next_if(Token::Drop) {
+            parsed.is_drop = true;
+            parsed.to_square = Some(self.square()?);
+            return Ok(parsed);
+        }
+
+        parsed.from_square = Some(self.square()?);
+        if self.next_if(Token::Take) {
+            parsed.is_take = true;
+        } else {
+            self.next_if(Token::Dash);
+        }
+        parsed.to_square = Some(self.square()?);
+
+        // promote piece, "=" is optional
+        let promote_position = self.position();
+        let has_promote_sign = self.next_if(Token::Promote);
+        match self.peek() {
+            Some(Token::Piece(promote_piece_type)) => {
+                self.index += 1;
+                // variant decides the promote piece types, ie. king in antichess
+                let is_valid =
+                    piece_type == PieceType::Pawn && promote_piece_type != PieceType::Pawn;
+                if !is_valid {
+                    return Err(ParseError::new(
+                        promote_position,
+                        ParseErrorKind::InvalidPromotion,
+                    ));
+                }
+                parsed.promote_piece_type = Some(promote_piece_type);
+            }
+            _ if has_promote_sign => {
+                return Err(ParseError::new(
+                    self.position(),
+                    ParseErrorKind::InvalidPromotion,
+                ))
+            }
+            _ => {}
+        }
+
+        Ok(parsed)
+    }
+
+    fn square(&mut self) -> Result<Square, ParseError> {
+        match self.peek() {
+            Some(Token::Square(square)) => {
+                self.index += 1;
+                Ok(square)
+            }
+            _ => Err(ParseError::new(
+                self.position(),
+                ParseErrorKind::ExpectedSquare,
+            )),
+        }
+    }
+
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.index).map(|(_, token)| *token)
+    }
+
+    /// consumes the next token if it is token
+    fn next_if(&mut self, token: Token) -> bool {
+        let is_match = self.peek() == Some(token);
+        if is_match {
+            self.index += 1;
+        }
+        is_match
+    }
+
+    /// position of the next token or end of the move string
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map_or(self.chars.len(), |(position, _)| *position)
+    }
+
+    fn unexpected_char(&self, position: usize) -> ParseError {
+        ParseError::new(
+            position,
+            ParseErrorKind::UnexpectedChar(self.chars[position]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notation::{MoveGlyph, ParseError, ParseErrorKind, ParsedMove};
+    use crate::pieces::king::KingCastleMoveResult;
+    use crate::pieces::piece::{PieceColor, PieceType};
+    use crate::square::Square;
+
+    #[test]
+    pub fn test_parse_move_str() {
+        let parsed: ParsedMove = "Ng1xf3+!?".parse().unwrap();
+        assert_eq!(PieceType::Knight, parsed.piece_type);
+        assert_eq!(Some("g1".parse::<Square>().unwrap()), parsed.from_square);
+        assert_eq!(Some("f3".parse::<Square>().unwrap()), parsed.to_square);
+        assert!(parsed.is_take && parsed.is_check && !parsed.is_checkmate);
+        assert_eq!(Some(MoveGlyph::Interesting), parsed.glyph);
+
+        let parsed: ParsedMove = "e7e8=Q#".parse().unwrap();
+        assert_eq!(PieceType::Pawn, parsed.piece_type);
+        assert_eq!(Some(PieceType::Queen), parsed.promote_piece_type);
+        assert!(parsed.is_checkmate);
+
+        // king promotion is left to the variant, ie. antichess
+        let parsed: ParsedMove = "a7a8=K".parse().unwrap();
+        assert_eq!(Some(PieceType::King), parsed.promote_piece_type);
+
+        for (move_str, castle) in [
+            ("0-0", KingCastleMoveResult::ShortCastle),
+            ("O-O+", KingCastleMoveResult::ShortCastle),
+            ("0-0-0", KingCastleMoveResult::LongCastle),
+            ("O-O-O??", KingCastleMoveResult::LongCastle),
+        ] {
+            let parsed: ParsedMove = move_str.parse().unwrap();
+            assert_eq!(Some(castle), parsed.castle);
+            assert_eq!(PieceType::King, parsed.piece_type);
+        }
+
+        let parsed: ParsedMove = "@e4".parse().unwrap();
+        assert!(parsed.is_drop);
+        assert_eq!(PieceType::Pawn, parsed.piece_type);
+        let (from_coord, to_coord) = parsed.coords(PieceColor::White);
+        assert_eq!(from_coord, to_coord);
+
+        // errors point at the offending char
+        for (move_str, position, kind) in [
+            ("", 0, ParseErrorKind::Empty),
+            ("e2e9", 2, ParseErrorKind::InvalidSquare),
+            ("Nf3", 3, ParseErrorKind::ExpectedSquare),
+            ("e2e4e5", 4, ParseErrorKind::UnexpectedChar('e')),
+            ("e2e4 ", 4, ParseErrorKind::UnexpectedChar(' ')),
+            ("e4-0-0", 3, ParseErrorKind::ExpectedSquare),
+            ("e2e40-0", 4, ParseErrorKind::UnexpectedChar('0')),
+            ("Ng1f3=Q", 5, ParseErrorKind::InvalidPromotion),
+            ("e7e8=P", 4, ParseErrorKind::InvalidPromotion),
+            ("e2e4+#", 5, ParseErrorKind::UnexpectedChar('#')),
+            ("e2e4!!!", 6, ParseErrorKind::UnexpectedChar('!')),
+            ("Z", 0, ParseErrorKind::UnexpectedChar('Z')),
+        ] {
+            assert_eq!(
+                Err(ParseError { position, kind }),
+                move_str.parse::<ParsedMove>(),
+                "{move_str}"
+            );
+        }
+    }
+}
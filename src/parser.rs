@@ -1,15 +1,16 @@
 use js_sys::Array;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 use crate::{
     board::Board,
-    console_log,
+    error::{ChessError, ChessResult},
+    fen::Fen,
+    notation::ParsedMove,
     pieces::{
-        king::KingCastleValidator,
+        king::KingCastleMoveResult,
         piece::{PieceColor, PieceType},
-        strategy::{MoveValidator, StrategyBuilder},
+        strategy::{MoveHandler, MoveValidator, StrategyBuilder},
     },
     tile::{TileCoord, TileFile, TileRank},
 };
@@ -33,28 +34,20 @@ impl MoveParser {
         move_writer.write_move(move_result)
     }
 
-    pub fn str_to_move_result(move_str: &str, piece_color: PieceColor) -> MoveResult {
+    pub fn str_to_move_result(
+        move_str: &str,
+        piece_color: PieceColor,
+    ) -> Result<MoveResult, JsValue> {
         let move_reader = MoveReader::default();
-        move_reader.parse_move(move_str, piece_color)
+        Ok(move_reader.parse_move(move_str, piece_color)?)
     }
 
-    pub fn js_split_all_moves(all_moves_str: String) -> Array {
-        let (white_moves, black_moves) = MoveReader::split_white_black_moves(all_moves_str);
-
-        let moves_arr = Array::new_with_length(white_moves.len() as u32);
-
-        for (i, white_move) in white_moves.iter().enumerate() {
-            let move_arr = Array::new_with_length(2_u32);
-            move_arr.set(0, white_move.into());
-
-            if let Some(black_move) = black_moves.get(i) {
-                move_arr.set(1, black_move.into());
-            }
-
-            moves_arr.set(i.try_into().unwrap(), move_arr.into());
-        }
-
-        moves_arr
+    /// JS array of move strings in ply order, see `MoveReader::split_moves`
+    pub fn js_split_all_moves(all_moves_str: &str) -> Array {
+        MoveReader::split_moves(all_moves_str)
+            .iter()
+            .map(|move_str| JsValue::from_str(move_str))
+            .collect()
     }
 }
 
@@ -73,6 +66,87 @@ pub struct MoveResult {
     pub is_check: bool,
     pub is_checkmate: bool,
     pub is_from_remote: bool,
+    /// piece dropped from pocket, from coord is the same as to coord
+    #[serde(default)]
+    pub is_drop: bool,
+    /// piece added to a pocket by this take in drop variants,
+    /// promoted pieces revert to pawns
+    #[serde(default)]
+    pub pocket_piece_type: Option<PieceType>,
+    /// every board change made by the move in order, see `js_effects`
+    #[serde(default)]
+    #[wasm_bindgen(skip)]
+    pub effects: Vec<MoveEffect>,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum MoveEffectKind {
+    /// piece moved from coord to coord
+    Move,
+    /// piece removed from coord, ie. taken or exploded
+    Remove,
+    /// pawn on coord replaced by promote piece
+    Promote,
+    /// piece dropped from pocket on coord
+    Drop,
+}
+
+/// Single board change made by a move,
+/// from coord is the same as to coord unless the piece moved
+#[wasm_bindgen]
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct MoveEffect {
+    pub kind: MoveEffectKind,
+    pub piece_type: PieceType,
+    pub piece_color: PieceColor,
+    pub from_coord: TileCoord,
+    pub to_coord: TileCoord,
+}
+
+impl MoveEffect {
+    pub fn moved(
+        piece_type: PieceType,
+        piece_color: PieceColor,
+        from_coord: TileCoord,
+        to_coord: TileCoord,
+    ) -> Self {
+        Self {
+            kind: MoveEffectKind::Move,
+            piece_type,
+            piece_color,
+            from_coord,
+            to_coord,
+        }
+    }
+
+    pub fn removed(piece_type: PieceType, piece_color: PieceColor, coord: TileCoord) -> Self {
+        Self::at(MoveEffectKind::Remove, piece_type, piece_color, coord)
+    }
+
+    /// piece type is the promote piece
+    pub fn promoted(piece_type: PieceType, piece_color: PieceColor, coord: TileCoord) -> Self {
+        Self::at(MoveEffectKind::Promote, piece_type, piece_color, coord)
+    }
+
+    pub fn dropped(piece_type: PieceType, piece_color: PieceColor, coord: TileCoord) -> Self {
+        Self::at(MoveEffectKind::Drop, piece_type, piece_color, coord)
+    }
+
+    fn at(
+        kind: MoveEffectKind,
+        piece_type: PieceType,
+        piece_color: PieceColor,
+        coord: TileCoord,
+    ) -> Self {
+        Self {
+            kind,
+            piece_type,
+            piece_color,
+            from_coord: coord,
+            to_coord: coord,
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -103,15 +177,30 @@ impl MoveResult {
             is_check,
             is_checkmate,
             is_from_remote: false,
+            is_drop: false,
+            pocket_piece_type: None,
+            effects: vec![],
         }
     }
 
-    pub fn to_json(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self).unwrap()
+    /// returns JS array of all MoveEffect in the order they are applied,
+    /// used to animate moves and apply them without validating the move
+    pub fn js_effects(&self) -> Array {
+        self.effects
+            .iter()
+            .map(|effect| JsValue::from(*effect))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        let json = serde_wasm_bindgen::to_value(&self)
+            .map_err(|err| ChessError::Serialize(err.to_string()))?;
+        Ok(json)
     }
 
-    pub fn from_json(json: JsValue) -> Self {
-        serde_wasm_bindgen::from_value(json).unwrap()
+    pub fn from_json(json: JsValue) -> Result<MoveResult, JsValue> {
+        let move_result = serde_wasm_bindgen::from_value(json).map_err(ChessError::from)?;
+        Ok(move_result)
     }
 
     pub fn set_promote_piece(&mut self, piece_type: PieceType) {
@@ -148,6 +237,10 @@ impl MoveWriter {
             return "0-0".to_string();
         };
 
+        if move_res.is_drop {
+            return self.write_drop(move_res);
+        }
+
         // get from_coord string
         let from_coord_str = {
             let coord = move_res.from_coord;
@@ -196,216 +289,188 @@ impl MoveWriter {
             piece_str, from_coord_str, take_str, to_coord_str, check_or_checkmate_str
         )
     }
-}
-
-#[wasm_bindgen]
-pub struct MoveReader {}
-
-#[wasm_bindgen]
-impl MoveReader {
-    #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    /// main method to parse move string into move a result
-    /// it is the opposite of write_move method
-    pub fn parse_move(&self, move_str: &str, piece_color: PieceColor) -> MoveResult {
-        // pub fn parse_move(&self, move_str: &str, piece_color: PieceColor, board: Board) -> MoveResult {
-        // check if is take
-        let is_take = move_str.contains('x');
 
-        MoveResult {
-            piece_type: self.get_piece_type(move_str),
-            piece_color,
-            from_coord: self.get_from_coord(move_str, piece_color),
-            to_coord: self.get_to_coord(move_str, piece_color),
-            promote_piece_type: self.get_promote_piece_type(move_str),
-            is_promote_piece: self.get_promote_piece_type(move_str).is_some(),
-            is_take,
-            is_short_castle: self.is_short_castle(move_str),
-            is_long_castle: self.is_long_castle(move_str),
-            is_check: self.is_check(move_str),
-            is_checkmate: self.is_checkmate(move_str),
-            is_from_remote: false,
-        }
-    }
+    /// write move in standard algebraic notation, ie. "Nbd7"
+    /// board must be the position before the move is made,
+    /// it is used to disambiguate pieces of the same type
+    pub fn write_san(&self, move_res: &MoveResult, board: &Board) -> String {
+        // add '+' to move string if check or '#' if checkmate
+        let check_or_checkmate_str = if move_res.is_checkmate {
+            "#"
+        } else if move_res.is_check {
+            "+"
+        } else {
+            ""
+        };
 
-    fn get_to_coord(&self, move_str: &str, piece_color: PieceColor) -> TileCoord {
-        // king castle possible
-        // return king coord move position if castle move
-        if self.is_long_castle(move_str) {
-            return KingCastleValidator::long_castle_coord(piece_color);
-        }
+        if move_res.is_long_castle {
+            return format!("O-O-O{check_or_checkmate_str}");
+        };
 
-        if self.is_short_castle(move_str) {
-            return KingCastleValidator::short_castle_coord(piece_color);
-        }
+        if move_res.is_short_castle {
+            return format!("O-O{check_or_checkmate_str}");
+        };
 
-        // remove last char if check or checkmate
-        let mut str_copy = move_str.to_string();
-        if self.is_check(move_str) || self.is_checkmate(move_str) {
-            str_copy.pop();
+        if move_res.is_drop {
+            return self.write_drop(move_res);
         }
 
-        // check if string contains promote piece on end
-        // remove if exists
-        let str_split = str_copy.split('=').collect::<Vec<&str>>();
-        let str_copy = str_split[0];
+        let take_str = if move_res.is_take { "x" } else { "" };
+        let to_coord_str = move_res.to_coord.algebraic();
 
-        // get to_coord
-        let to_rank: TileRank = if let Some(rank_char) = str_copy.chars().rev().next() {
-            rank_char.into()
-        } else {
-            TileRank::Unknown
-        };
-        let to_file = if let Some(file_char) = str_copy.chars().rev().nth(1) {
-            file_char.into()
-        } else {
-            TileFile::Unknown
-        };
-
-        TileCoord::new(to_rank.into(), to_file.into())
-    }
+        if move_res.piece_type == PieceType::Pawn {
+            // pawn takes are written with the from file
+            let from_file_str = if move_res.is_take {
+                move_res.from_coord.file().to_string()
+            } else {
+                "".to_string()
+            };
 
-    fn get_from_coord(&self, move_str: &str, piece_color: PieceColor) -> TileCoord {
-        // return none if castle move
-        // king castle possible
-        // return king coord move position if castle move
-        if self.is_long_castle(move_str) || self.is_short_castle(move_str) {
-            return KingCastleValidator::king_start_coord(piece_color);
-        }
+            let promote_str = match move_res.promote_piece_type {
+                Some(piece_type) => format!("={piece_type}"),
+                None => "".to_string(),
+            };
 
-        // remove last char if check or checkmate
-        let mut str_copy = move_str.to_string();
-        if self.is_check(move_str) || self.is_checkmate(move_str) {
-            str_copy.pop();
+            return format!(
+                "{from_file_str}{take_str}{to_coord_str}{promote_str}{check_or_checkmate_str}"
+            );
         }
 
-        // get starting index based on piece type
-        // if piece type is pawn then starting index is 0 otherwise it is 1
-        let start_index = usize::from(self.get_piece_type(move_str) != PieceType::Pawn);
+        format!(
+            "{}{}{}{}{}",
+            move_res.piece_type,
+            self.san_disambiguation(move_res, board),
+            take_str,
+            to_coord_str,
+            check_or_checkmate_str
+        )
+    }
 
-        // get from coord
-        let from_file = if let Some(file_char) = move_str.chars().nth(start_index) {
-            file_char.into()
-        } else {
-            TileFile::Unknown
-        };
-        let from_rank = if let Some(rank_char) = move_str.chars().nth(start_index + 1) {
-            rank_char.into()
+    /// drop move string, ie. "N@f3", pawn drops are written "P@e4"
+    fn write_drop(&self, move_res: &MoveResult) -> String {
+        let check_or_checkmate_str = if move_res.is_checkmate {
+            "#"
+        } else if move_res.is_check {
+            "+"
         } else {
-            TileRank::Unknown
+            ""
         };
 
-        TileCoord::new(from_rank.into(), from_file.into())
+        format!(
+            "{}@{}{}",
+            Fen::piece_char(move_res.piece_type, PieceColor::White),
+            move_res.to_coord.algebraic(),
+            check_or_checkmate_str
+        )
     }
 
-    fn get_piece_type(&self, move_str: &str) -> PieceType {
-        // get piece type
-        let piece_type = if let Some(piece_char) = move_str.chars().next() {
-            if piece_char.is_uppercase() {
-                piece_char.into()
-            } else {
-                PieceType::Pawn
+    /// returns from file, rank or both if another piece
+    /// of the same type and color can move to the same tile
+    fn san_disambiguation(&self, move_res: &MoveResult, board: &Board) -> String {
+        let from_coord = move_res.from_coord;
+        let mut same_file = false;
+        let mut same_rank = false;
+        let mut is_ambiguous = false;
+
+        for coord in MoveHandler::own_piece_coords(move_res.piece_color, board) {
+            if coord == from_coord {
+                continue;
             }
-        } else {
-            PieceType::Pawn
-        };
 
-        piece_type
-    }
+            // SAFETY:
+            // tile has piece, confirmed in own_piece_coords
+            let piece = board.peek_tile(&coord).unwrap();
+            if piece.piece_type() != move_res.piece_type {
+                continue;
+            }
 
-    fn get_promote_piece_type(&self, move_str: &str) -> Option<PieceType> {
-        if move_str.contains('=') {
-            if self.is_check(move_str) || self.is_checkmate(move_str) {
-                // remove last char if check or checkmate
-                let mut str_copy = move_str.to_string();
-                str_copy.pop();
-                return Some(str_copy.chars().last().unwrap().into());
+            let strategy = StrategyBuilder::new_piece_strategy(
+                piece.piece_type(),
+                coord,
+                piece.color(),
+                board,
+            );
+            let validator = MoveValidator::new(move_res.to_coord, board);
+
+            if validator.is_valid_move(strategy.as_ref(), false) {
+                is_ambiguous = true;
+                same_file |= coord.col() == from_coord.col();
+                same_rank |= coord.row() == from_coord.row();
             }
-            return Some(move_str.chars().last().unwrap().into());
-        } else {
-            None
         }
-    }
 
-    fn is_long_castle(&self, move_str: &str) -> bool {
-        // is long castle
-        let re = Regex::new(r"0-0-0").unwrap();
-        re.is_match(move_str)
-    }
-
-    fn is_short_castle(&self, move_str: &str) -> bool {
-        // is short castle
-        if move_str.split('-').count() > 2 {
-            return false;
+        match (is_ambiguous, same_file, same_rank) {
+            (false, _, _) => "".to_string(),
+            (true, false, _) => from_coord.file().to_string(),
+            (true, true, false) => from_coord.rank().to_string(),
+            (true, true, true) => from_coord.algebraic(),
         }
-        let re = Regex::new(r"0-0").unwrap();
-        re.is_match(move_str)
     }
+}
 
-    fn is_check(&self, move_str: &str) -> bool {
-        // is king in check
-        move_str.ends_with('+')
-    }
+#[wasm_bindgen]
+pub struct MoveReader {}
 
-    fn is_checkmate(&self, move_str: &str) -> bool {
-        // is checkmate
-        move_str.ends_with('#')
+#[wasm_bindgen]
+impl MoveReader {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {}
     }
 }
 
-pub type WhiteBlackMovesSplit = (Vec<String>, Vec<String>);
-
 impl MoveReader {
-    pub fn split_white_black_moves(all_moves_str: String) -> WhiteBlackMovesSplit {
-        let mut black_moves = Vec::new();
-        let mut white_moves = Vec::new();
-
-        // get each pair of moves
-        let move_pairs: Vec<&str> = all_moves_str.split(',').collect();
-
-        for move_pair in move_pairs.iter() {
-            // split pair by space
-            let pair_split: Vec<&str> = move_pair.split(' ').collect();
-
-            // remove number from beginning of string
-            if let Some(&white_move_str_with_num) = pair_split.first() {
-                let move_num_split: Vec<&str> = white_move_str_with_num.split('.').collect();
-
-                // insert white move
-                if let Some(&white_move_str) = move_num_split.get(1) {
-                    white_moves.push(white_move_str.to_string());
-                }
-            }
-
-            // check if black move exists
-            if let Some(&black_move_str) = pair_split.get(1) {
-                // insert black move
-                black_moves.push(black_move_str.to_string());
-            }
-        }
-
-        (white_moves, black_moves)
+    /// main method to parse move string into a move result
+    /// it is the opposite of write_move method,
+    /// malformed move strings are rejected, see `ParsedMove`
+    pub fn parse_move(&self, move_str: &str, piece_color: PieceColor) -> ChessResult<MoveResult> {
+        let parsed: ParsedMove = move_str
+            .parse()
+            .map_err(|err| ChessError::InvalidMoveStr(move_str.to_string(), err))?;
+        let (from_coord, to_coord) = parsed.coords(piece_color);
+
+        Ok(MoveResult {
+            piece_type: parsed.piece_type,
+            piece_color,
+            from_coord,
+            to_coord,
+            promote_piece_type: parsed.promote_piece_type,
+            is_promote_piece: parsed.promote_piece_type.is_some(),
+            is_take: parsed.is_take,
+            is_short_castle: parsed.castle == Some(KingCastleMoveResult::ShortCastle),
+            is_long_castle: parsed.castle == Some(KingCastleMoveResult::LongCastle),
+            is_check: parsed.is_check,
+            is_checkmate: parsed.is_checkmate,
+            is_from_remote: false,
+            is_drop: parsed.is_drop,
+            pocket_piece_type: None,
+            effects: vec![],
+        })
     }
 
-    fn parse_moves(&self, all_moves_str: String) -> Vec<MoveResult> {
-        let (white_moves, black_moves) = MoveReader::split_white_black_moves(all_moves_str);
-
-        let mut move_results = vec![];
+    /// split moves string into list of moves in ply order
+    /// move numbers are removed, ie. "1.e2e4 e7e5,2.g1f3" or "1...e7e5,2.g1f3"
+    pub fn split_moves(all_moves_str: &str) -> Vec<String> {
+        all_moves_str
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(MoveReader::strip_move_number)
+            .filter(|move_str| !move_str.is_empty())
+            .map(|move_str| move_str.to_string())
+            .collect()
+    }
 
-        for (i, move_str) in white_moves.iter().enumerate() {
-            // add result to array
-            move_results.push(self.parse_move(move_str, PieceColor::White));
+    /// remove leading move number, ie. "12." or "12..."
+    /// castle moves like "0-0" are left untouched
+    fn strip_move_number(token: &str) -> &str {
+        let digits_end = token
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(token.len());
 
-            // check if there is a corresponding black move to add
-            if let Some(black_move_str) = black_moves.get(i) {
-                move_results.push(self.parse_move(black_move_str, PieceColor::Black));
-            }
+        if digits_end > 0 && token[digits_end..].starts_with('.') {
+            token[digits_end..].trim_start_matches('.')
+        } else {
+            token
         }
-
-        move_results
     }
 }
 
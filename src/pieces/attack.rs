@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::pieces::pawn::PawnMoveStrategy;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::pieces::strategy::{MoveHandler, PieceMoveStrategy, StrategyBuilder};
+use crate::square::Square;
+use crate::tile::TileCoord;
+
+/// Number of white and black attackers of each tile,
+/// indexed the same as the board tiles
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ControlMap {
+    white: Vec<u8>,
+    black: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl ControlMap {
+    pub fn new(board: &Board) -> Self {
+        let mut control_map = Self {
+            white: vec![0; 64],
+            black: vec![0; 64],
+        };
+
+        for piece_color in [PieceColor::White, PieceColor::Black] {
+            for piece_coord in MoveHandler::own_piece_coords(piece_color, board) {
+                // SAFETY:
+                // tile has piece, confirmed by own piece coords
+                let piece = board.peek_tile(&piece_coord).unwrap();
+                let piece_strategy = StrategyBuilder::new_piece_strategy(
+                    piece.piece_type(),
+                    piece_coord,
+                    piece_color,
+                    board,
+                );
+
+                for i in 0..board.num_tiles() {
+                    let coord: TileCoord = i.into();
+                    if AttackValidator::is_attack(piece_strategy.as_ref(), coord, board) {
+                        control_map.counts_mut(piece_color)[i] += 1;
+                    }
+                }
+            }
+        }
+
+        control_map
+    }
+
+    /// number of pieces of piece color attacking coord
+    pub fn count(&self, coord: &TileCoord, piece_color: PieceColor) -> u8 {
+        self.counts(piece_color)[Board::tile_idx_from_coord(coord)]
+    }
+
+    /// returns attacker count of all 64 tiles for piece color
+    pub fn counts(&self, piece_color: PieceColor) -> Vec<u8> {
+        match piece_color {
+            PieceColor::White => self.white.clone(),
+            PieceColor::Black => self.black.clone(),
+        }
+    }
+
+    /// color with the most attackers of coord,
+    /// None if neither or both control it equally
+    pub fn controlled_by(&self, coord: &TileCoord) -> Option<PieceColor> {
+        let white = self.count(coord, PieceColor::White);
+        let black = self.count(coord, PieceColor::Black);
+
+        match white.cmp(&black) {
+            std::cmp::Ordering::Greater => Some(PieceColor::White),
+            std::cmp::Ordering::Less => Some(PieceColor::Black),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+impl ControlMap {
+    fn counts_mut(&mut self, piece_color: PieceColor) -> &mut Vec<u8> {
+        match piece_color {
+            PieceColor::White => &mut self.white,
+            PieceColor::Black => &mut self.black,
+        }
+    }
+}
+
+pub struct AttackValidator {}
+
+impl AttackValidator {
+    /// check if piece attacks coord, ie. could take a piece on it,
+    /// pawns only attack diagonally and the king does not attack its castle targets
+    pub fn is_attack(
+        piece_strategy: &dyn PieceMoveStrategy,
+        coord: TileCoord,
+        board: &Board,
+    ) -> bool {
+        AttackValidator::is_attack_without(piece_strategy, coord, board, &[])
+    }
+
+    /// same as `is_attack` with the removed coords treated as empty tiles
+    pub fn is_attack_without(
+        piece_strategy: &dyn PieceMoveStrategy,
+        coord: TileCoord,
+        board: &Board,
+        removed: &[TileCoord],
+    ) -> bool {
+        let piece_coord = piece_strategy.coord();
+        if piece_coord == coord || removed.contains(&piece_coord) {
+            return false;
+        }
+
+        let is_attack_move = match piece_strategy.piece_type() {
+            PieceType::Pawn => {
+                PawnMoveStrategy::diagonal_moves(piece_strategy.color(), piece_coord)
+                    .contains(&coord)
+            }
+            PieceType::King => {
+                piece_coord.row().abs_diff(coord.row()) <= 1
+                    && piece_coord.col().abs_diff(coord.col()) <= 1
+            }
+            _ => piece_strategy.moves().contains(&coord),
+        };
+
+        if !is_attack_move {
+            return false;
+        }
+
+        // knights jump over pieces
+        piece_strategy.piece_type() == PieceType::Knight
+            || piece_strategy
+                .tiles_between(coord)
+                .iter()
+                .all(|between| removed.contains(between) || board.peek_tile(between).is_none())
+    }
+
+    /// coords of all pieces of piece color attacking coord
+    pub fn attackers_of(
+        coord: TileCoord,
+        piece_color: PieceColor,
+        board: &Board,
+    ) -> Vec<TileCoord> {
+        AttackValidator::attackers_without(coord, piece_color, board, &[])
+    }
+
+    /// check if any piece of by color attacks coord
+    pub fn is_attacked(coord: TileCoord, by_color: PieceColor, board: &Board) -> bool {
+        !AttackValidator::attackers_of(coord, by_color, board).is_empty()
+    }
+
+    /// same as `is_attacked` with the removed coords treated as empty tiles,
+    /// used to check tiles the king moves to without copying the board
+    pub fn is_attacked_without(
+        coord: TileCoord,
+        by_color: PieceColor,
+        board: &Board,
+        removed: &[TileCoord],
+    ) -> bool {
+        !AttackValidator::attackers_without(coord, by_color, board, removed).is_empty()
+    }
+
+    /// coord of the king of piece color, None in variants without king, eg. horde
+    pub fn king_coord(piece_color: PieceColor, board: &Board) -> Option<TileCoord> {
+        (0..board.num_tiles()).map(TileCoord::from).find(|coord| {
+            board.peek_tile(coord).is_some_and(|piece| {
+                piece.piece_type() == PieceType::King && piece.color() == piece_color
+            })
+        })
+    }
+
+    /// coords of all enemy pieces giving check to king of piece color
+    pub fn checkers(piece_color: PieceColor, board: &Board) -> Vec<TileCoord> {
+        match AttackValidator::king_coord(piece_color, board) {
+            Some(king_coord) => AttackValidator::attackers_of(
+                king_coord,
+                PieceColor::opposite_color(piece_color),
+                board,
+            ),
+            None => vec![],
+        }
+    }
+
+    /// coord of the enemy piece pinning piece at coord to its king,
+    /// pinned piece can only move along the line to the pinning piece
+    pub fn pinned_by(coord: TileCoord, king_coord: TileCoord, board: &Board) -> Option<TileCoord> {
+        let piece = board.peek_tile(&coord)?;
+        let square = Square::try_from(coord).ok()?;
+        let direction = Square::try_from(king_coord).ok()?.direction_to(square)?;
+
+        // tiles between king and piece must be empty
+        if !AttackValidator::tiles_between(king_coord, coord)
+            .iter()
+            .all(|between| board.peek_tile(between).is_none())
+        {
+            return None;
+        }
+
+        // first piece behind the pinned piece must be an enemy slider on the same line
+        let (behind_coord, behind) = square
+            .ray(direction)
+            .map(TileCoord::from)
+            .find_map(|behind_coord| Some((behind_coord, board.peek_tile(&behind_coord)?)))?;
+
+        let is_slider = match behind.piece_type() {
+            PieceType::Queen => true,
+            PieceType::Bishop => direction.is_diagonal(),
+            PieceType::Rook => !direction.is_diagonal(),
+            _ => false,
+        };
+
+        if is_slider && behind.color() != piece.color() {
+            return Some(behind_coord);
+        }
+        None
+    }
+
+    /// all coords strictly between two coords on the same row, col or diagonal,
+    /// empty if the coords are not on a line
+    pub fn tiles_between(from_coord: TileCoord, to_coord: TileCoord) -> Vec<TileCoord> {
+        match (Square::try_from(from_coord), Square::try_from(to_coord)) {
+            (Ok(from_square), Ok(to_square)) => from_square
+                .between(to_square)
+                .into_iter()
+                .map(TileCoord::from)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    fn attackers_without(
+        coord: TileCoord,
+        piece_color: PieceColor,
+        board: &Board,
+        removed: &[TileCoord],
+    ) -> Vec<TileCoord> {
+        MoveHandler::own_piece_coords(piece_color, board)
+            .into_iter()
+            .filter(|piece_coord| {
+                // SAFETY:
+                // tile has piece, confirmed by own piece coords
+                let piece = board.peek_tile(piece_coord).unwrap();
+                let piece_strategy = StrategyBuilder::new_piece_strategy(
+                    piece.piece_type(),
+                    *piece_coord,
+                    piece_color,
+                    board,
+                );
+                AttackValidator::is_attack_without(piece_strategy.as_ref(), coord, board, removed)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::pieces::piece::PieceColor;
+    use crate::square::Square;
+    use crate::tile::TileCoord;
+
+    #[test]
+    pub fn test_attackers_of() {
+        let board = Board::new();
+        let f3 = TileCoord::new(2, 5);
+
+        // knight on g1, pawns on e2 and g2
+        let mut attackers = board.attackers_of(f3, PieceColor::White);
+        attackers.sort();
+        assert_eq!(
+            vec![
+                TileCoord::new(0, 6),
+                TileCoord::new(1, 4),
+                TileCoord::new(1, 6)
+            ],
+            attackers
+        );
+        assert!(!board.is_attacked(f3, PieceColor::Black));
+
+        // pawns do not attack the tile in front of them
+        assert!(!board.is_attacked(TileCoord::new(3, 4), PieceColor::White));
+
+        let control_map = board.control_map();
+        assert_eq!(3, control_map.count(&f3, PieceColor::White));
+        assert_eq!(Some(PieceColor::White), control_map.controlled_by(&f3));
+        assert_eq!(None, control_map.controlled_by(&TileCoord::new(3, 4)));
+    }
+
+    #[test]
+    pub fn test_castle_through_attack() {
+        // black rook attacks f1, king would pass over it castling short
+        let fen = "4kr2/8/8/8/8/8/8/R3K2R w KQ - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        let king = TileCoord::new(0, 4);
+
+        assert!(board
+            .pre_move_result(king, TileCoord::new(0, 6), None)
+            .is_none());
+        assert!(board.move_piece(king, TileCoord::new(0, 2), None).is_some());
+
+        // white rook attacks d8, black king passes over it castling long
+        let fen = "r3k2r/8/8/8/8/8/8/3RK3 b kq - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        let king = TileCoord::new(7, 4);
+
+        assert!(board
+            .pre_move_result(king, TileCoord::new(7, 2), None)
+            .is_none());
+        assert!(board
+            .pre_move_result(king, TileCoord::new(7, 6), None)
+            .is_some());
+
+        // cannot castle out of check
+        let fen = "r3k2r/8/8/8/8/8/8/4R1K1 b kq - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        assert!(board
+            .pre_move_result(king, TileCoord::new(7, 6), None)
+            .is_none());
+    }
+
+    #[test]
+    pub fn test_pins_and_check_evasion() {
+        let coord = |algebraic: &str| -> TileCoord { algebraic.parse::<Square>().unwrap().into() };
+        let is_legal = |fen: &str, from: &str, to: &str| {
+            let mut board = Board::from_fen(fen).unwrap();
+            board
+                .pre_move_result(coord(from), coord(to), None)
+                .is_some()
+        };
+
+        // rook pinned on the e-file can only move along it
+        let fen = "4k3/4r3/8/8/8/8/4R3/4K3 w - - 0 1";
+        assert!(is_legal(fen, "e2", "e5"));
+        assert!(is_legal(fen, "e2", "e7"));
+        assert!(!is_legal(fen, "e2", "d2"));
+
+        // check must be blocked or the checking piece taken
+        let fen = "4k3/8/8/8/8/8/1R6/r3K3 w - - 0 1";
+        assert!(is_legal(fen, "b2", "b1"));
+        assert!(!is_legal(fen, "b2", "b5"));
+        assert!(!is_legal(fen, "e1", "d1"));
+
+        // double check, only the king can move
+        let fen = "4k3/8/8/8/3Q4/5n2/8/r3K3 w - - 0 1";
+        assert!(!is_legal(fen, "d4", "f3"));
+        assert!(is_legal(fen, "e1", "e2"));
+    }
+}
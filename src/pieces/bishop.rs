@@ -1,38 +1,22 @@
 // use crate::console_log;
-use crate::board::Board;
 use crate::pieces::piece::{PieceColor, PieceType};
-use crate::pieces::strategy::{PieceMoveStrategy, TilesBetween};
+use crate::pieces::strategy::PieceMoveStrategy;
+use crate::square::Direction;
 use crate::tile::TileCoord;
 
 pub struct BishopMoveStrategy {
     pub color: PieceColor,
     pub coord: TileCoord,
     pub piece_type: PieceType,
-    pub board: *const Board,
 }
 
 impl PieceMoveStrategy for BishopMoveStrategy {
     fn tiles_between(&self, new_coord: TileCoord) -> Vec<TileCoord> {
-        let moves = self.diag_intersect();
-        let direction = self.move_direction(new_coord);
-
-        moves.tiles_between(direction, self.coord(), new_coord)
+        self.ray_tiles_between(new_coord, &Direction::DIAGONAL)
     }
 
     fn moves(&self) -> Vec<TileCoord> {
-        let mut moves_vec = vec![];
-
-        let moves = self.diag_intersect();
-
-        for coord in moves.left_diag {
-            moves_vec.push(coord);
-        }
-
-        for coord in moves.right_diag {
-            moves_vec.push(coord);
-        }
-
-        moves_vec
+        self.ray_moves(&Direction::DIAGONAL)
     }
 
     fn coord(&self) -> TileCoord {
@@ -1,12 +1,10 @@
-use wasm_bindgen::prelude::*;
-// use crate::console_log;
-use crate::console_log;
 use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
 
 use crate::board::Board;
 use crate::pieces::piece::{PieceColor, PieceType};
-use crate::pieces::rook::RookFile;
-use crate::pieces::strategy::{MoveHandler, MoveValidator, PieceMoveStrategy, StrategyBuilder};
+use crate::pieces::strategy::{MoveValidator, PieceMoveStrategy};
+use crate::square::Direction;
 use crate::tile::TileCoord;
 
 pub struct KingMoveStrategy {
@@ -16,90 +14,55 @@ pub struct KingMoveStrategy {
     pub board: *const Board,
 }
 
-impl PieceMoveStrategy for KingMoveStrategy {
-    fn tiles_between(&self, new_coord: TileCoord) -> Vec<TileCoord> {
-        let mut tiles: Vec<TileCoord> = vec![];
-
-        match self.color {
-            // white king
-            PieceColor::White => {
-                // long castle
-                if new_coord.col() == 1 {
-                    tiles.push(TileCoord::new(0, 2));
-                    tiles.push(TileCoord::new(0, 3));
-                }
-
-                // short castle
-                if new_coord.col() == 6 {
-                    tiles.push(TileCoord::new(0, 5));
-                }
-            }
-
-            // black king
-            PieceColor::Black => {
-                // long castle
-                if new_coord.col() == 1 {
-                    tiles.push(TileCoord::new(7, 2));
-                    tiles.push(TileCoord::new(7, 3));
-                }
-
-                // short castle
-                if new_coord.col() == 6 {
-                    tiles.push(TileCoord::new(7, 5));
-                }
-            }
-        }
+impl KingMoveStrategy {
+    fn board(&self) -> &Board {
+        unsafe { self.board.as_ref().unwrap() }
+    }
+}
 
-        tiles
+impl PieceMoveStrategy for KingMoveStrategy {
+    /// king only moves one tile, castle move
+    /// path is validated by the castle validator
+    fn tiles_between(&self, _new_coord: TileCoord) -> Vec<TileCoord> {
+        vec![]
     }
 
     fn moves(&self) -> Vec<TileCoord> {
-        let mut moves_vec = vec![];
-        let (row, col) = self.coord().row_col();
-
-        let move_1 = TileCoord::new(row - 1, col);
-        let move_2 = TileCoord::new(row + 1, col);
-        let move_3 = TileCoord::new(row, col + 1);
-        let move_4 = TileCoord::new(row, col - 1);
-
-        let move_5 = TileCoord::new(row - 1, col + 1);
-        let move_6 = TileCoord::new(row + 1, col - 1);
-        let move_7 = TileCoord::new(row - 1, col - 1);
-        let move_8 = TileCoord::new(row + 1, col + 1);
+        let Some(square) = self.square() else {
+            return vec![];
+        };
 
-        if move_1.in_bounds() {
-            moves_vec.push(move_1)
-        }
-        if move_2.in_bounds() {
-            moves_vec.push(move_2)
-        }
-        if move_3.in_bounds() {
-            moves_vec.push(move_3)
-        }
-        if move_4.in_bounds() {
-            moves_vec.push(move_4)
-        }
-        if move_5.in_bounds() {
-            moves_vec.push(move_5)
-        }
-        if move_6.in_bounds() {
-            moves_vec.push(move_6)
-        }
-        if move_7.in_bounds() {
-            moves_vec.push(move_7)
-        }
-        if move_8.in_bounds() {
-            moves_vec.push(move_8)
-        }
+        let mut moves_vec: Vec<TileCoord> = Direction::ALL
+            .iter()
+            .filter_map(|direction| square.step(*direction))
+            .map(TileCoord::from)
+            .collect();
 
         // add king castle moves
-        let king_start_coord = KingCastleValidator::king_start_coord(self.color);
+        let castle_state = self.board().king_castle_state();
 
         // check if king is at starting position
-        if self.coord.row() == king_start_coord.row() && self.coord.col() == king_start_coord.col()
+        if self.board().variant_rules().has_castling()
+            && self.coord == castle_state.king_start_coord(self.color)
         {
-            moves_vec.push(KingCastleValidator::long_castle_coord(self.color));
-            moves_vec.push(KingCastleValidator::short_castle_coord(self.color));
+            for side in [
+                KingCastleMoveResult::LongCastle,
+                KingCastleMoveResult::ShortCastle,
+            ] {
+                // castle target is only a castle move if it
+                // cannot be reached with a normal king move
+                let target = KingCastleValidator::castle_coord(self.color, &side);
+                if target.col().abs_diff(self.coord.col()) >= 2 {
+                    moves_vec.push(target);
+                }
+
+                // chess960 castle moves are made by moving the king onto the rook
+                if castle_state.chess960
+                    && castle_state.has_castle_rook(self.color, &side, self.board())
+                {
+                    moves_vec.push(castle_state.rook_start_coord(self.color, &side));
+                }
+            }
         }
 
         moves_vec
@@ -118,11 +81,48 @@ impl PieceMoveStrategy for KingMoveStrategy {
     }
 }
 
+/// Start files of the king and castling rooks
+/// standard chess has the king on the e-file
+/// and the rooks on the a and h files
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug, Copy, PartialEq, Eq)]
+pub struct CastleFiles {
+    pub king_col: u8,
+    pub long_rook_col: u8,
+    pub short_rook_col: u8,
+}
+
+impl CastleFiles {
+    pub fn new(king_col: u8, long_rook_col: u8, short_rook_col: u8) -> Self {
+        Self {
+            king_col,
+            long_rook_col,
+            short_rook_col,
+        }
+    }
+
+    pub fn rook_col(&self, side: &KingCastleMoveResult) -> u8 {
+        match side {
+            KingCastleMoveResult::LongCastle => self.long_rook_col,
+            KingCastleMoveResult::ShortCastle => self.short_rook_col,
+        }
+    }
+}
+
+impl Default for CastleFiles {
+    fn default() -> Self {
+        Self::new(4, 0, 7)
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct KingCastleBoardState {
     pub white_king: KingCastleState,
     pub black_king: KingCastleState,
+    /// chess960 castle rules, king and rooks may start on any file
+    pub chess960: bool,
+    pub files: CastleFiles,
 }
 
 impl Default for KingCastleBoardState {
@@ -136,9 +136,130 @@ impl KingCastleBoardState {
         Self {
             white_king: KingCastleState::default(),
             black_king: KingCastleState::default(),
+            chess960: false,
+            files: CastleFiles::default(),
+        }
+    }
+
+    pub fn king_state(&self, piece_color: PieceColor) -> &KingCastleState {
+        match piece_color {
+            PieceColor::White => &self.white_king,
+            PieceColor::Black => &self.black_king,
+        }
+    }
+
+    pub fn king_state_mut(&mut self, piece_color: PieceColor) -> &mut KingCastleState {
+        match piece_color {
+            PieceColor::White => &mut self.white_king,
+            PieceColor::Black => &mut self.black_king,
         }
     }
 
+    pub fn king_start_coord(&self, piece_color: PieceColor) -> TileCoord {
+        TileCoord::new(
+            KingCastleValidator::back_row(piece_color),
+            self.files.king_col,
+        )
+    }
+
+    pub fn rook_start_coord(
+        &self,
+        piece_color: PieceColor,
+        side: &KingCastleMoveResult,
+    ) -> TileCoord {
+        TileCoord::new(
+            KingCastleValidator::back_row(piece_color),
+            self.files.rook_col(side),
+        )
+    }
+
+    /// returns castle side if moving king from old coord to new coord is a castle move
+    /// castle moves are made by moving the king to its castle target coord
+    /// or by moving the king onto the castling rook
+    pub fn castle_side(
+        &self,
+        piece_color: PieceColor,
+        old_coord: TileCoord,
+        new_coord: TileCoord,
+        board: &Board,
+    ) -> Option<KingCastleMoveResult> {
+        if old_coord != self.king_start_coord(piece_color) {
+            return None;
+        }
+
+        for side in [
+            KingCastleMoveResult::LongCastle,
+            KingCastleMoveResult::ShortCastle,
+        ] {
+            // without the right or rook it is a normal king move, ie. chess960 Kb1-a1
+            if new_coord == self.rook_start_coord(piece_color, &side)
+                && self.has_castle_rook(piece_color, &side, board)
+            {
+                return Some(side);
+            }
+
+            // target next to king is a normal king move
+            if new_coord == KingCastleValidator::castle_coord(piece_color, &side)
+                && new_coord.col().abs_diff(old_coord.col()) >= 2
+            {
+                return Some(side);
+            }
+        }
+
+        None
+    }
+
+    /// castling right of side is kept and an own rook
+    /// stands on the rook start coord
+    pub fn has_castle_rook(
+        &self,
+        piece_color: PieceColor,
+        side: &KingCastleMoveResult,
+        board: &Board,
+    ) -> bool {
+        let king_state = self.king_state(piece_color);
+        let is_rook_moved = match side {
+            KingCastleMoveResult::LongCastle => king_state.a_file_rook_moved,
+            KingCastleMoveResult::ShortCastle => king_state.h_file_rook_moved,
+        };
+
+        if king_state.is_king_moved || is_rook_moved {
+            return false;
+        }
+
+        board
+            .peek_tile(&self.rook_start_coord(piece_color, side))
+            .is_some_and(|piece| {
+                piece.piece_type() == PieceType::Rook && piece.color() == piece_color
+            })
+    }
+
+    /// castling rights are lost for good once the king or a castling rook
+    /// moves, or a piece is taken on the start coord of a castling rook
+    pub fn update_rights(&mut self, old_coord: TileCoord, new_coord: TileCoord) {
+        for color in [PieceColor::White, PieceColor::Black] {
+            let king_coord = self.king_start_coord(color);
+            let a_rook_coord = self.rook_start_coord(color, &KingCastleMoveResult::LongCastle);
+            let h_rook_coord = self.rook_start_coord(color, &KingCastleMoveResult::ShortCastle);
+
+            let king = self.king_state_mut(color);
+
+            if old_coord == king_coord {
+                king.is_king_moved = true
+            }
+
+            if old_coord == a_rook_coord || new_coord == a_rook_coord {
+                king.a_file_rook_moved = true
+            }
+
+            if old_coord == h_rook_coord || new_coord == h_rook_coord {
+                king.h_file_rook_moved = true
+            }
+        }
+    }
+
+    /// removes castling rights of pieces no longer on their start coord,
+    /// catches pieces removed without moving, eg. atomic explosions
     pub fn update_state(&mut self, board: *const Board) {
         // SAFETY:
         // this struct is only ever used from within the board
@@ -147,31 +268,27 @@ impl KingCastleBoardState {
         let board = unsafe { board.as_ref().unwrap() };
 
         for color in [PieceColor::White, PieceColor::Black] {
-            let mut king = if color == PieceColor::White {
-                &mut self.white_king
-            } else {
-                &mut self.black_king
-            };
+            let king_coord = self.king_start_coord(color);
+            let a_rook_coord = self.rook_start_coord(color, &KingCastleMoveResult::LongCastle);
+            let h_rook_coord = self.rook_start_coord(color, &KingCastleMoveResult::ShortCastle);
 
-            if KingCastleBoardState::is_king_moved(color, board) {
+            let king = self.king_state_mut(color);
+
+            if KingCastleBoardState::is_moved(PieceType::King, king_coord, color, board) {
                 king.is_king_moved = true
             }
 
             // check a rook
-            if KingCastleBoardState::is_rook_moved(RookFile::AFile, color, board) {
+            if KingCastleBoardState::is_moved(PieceType::Rook, a_rook_coord, color, board) {
                 king.a_file_rook_moved = true
             }
 
             // check h rook
-            if KingCastleBoardState::is_rook_moved(RookFile::HFile, color, board) {
+            if KingCastleBoardState::is_moved(PieceType::Rook, h_rook_coord, color, board) {
                 king.h_file_rook_moved = true
             }
 
-            if KingCastleBoardState::is_king_check(color, board) {
-                king.is_in_check = true
-            } else {
-                king.is_in_check = false
-            }
+            king.is_in_check = MoveValidator::is_check(color, board);
         }
     }
 
@@ -179,93 +296,22 @@ impl KingCastleBoardState {
     // Static Methods
     // ---
 
-    fn is_king_moved(piece_color: PieceColor, board: &Board) -> bool {
-        match piece_color {
-            // check white king coord
-            PieceColor::White => {
-                if board.peek_tile(&TileCoord::new(0, 4)).is_none() {
-                    return true;
-                }
-            }
-
-            // check black king coord
-            PieceColor::Black => {
-                if board.peek_tile(&TileCoord::new(7, 4)).is_none() {
-                    return true;
-                }
-            }
-        }
-
-        // return king not moved if none of above cases
-        false
-    }
-
-    fn is_rook_moved(rook_file: RookFile, piece_color: PieceColor, board: &Board) -> bool {
-        match piece_color {
-            PieceColor::White => {
-                // get rook coord
-                let coord = match rook_file {
-                    RookFile::AFile => TileCoord::new(0, 0),
-                    RookFile::HFile => TileCoord::new(0, 7),
-                };
-
-                //rook moved
-                if board.peek_tile(&coord).is_none() {
-                    return true;
-                }
-
-                // rook taken
-                if let Some(piece) = board.peek_tile(&coord) {
-                    if piece.color() != piece_color {
-                        return true;
-                    }
-                }
-            }
-
-            PieceColor::Black => {
-                // get rook coord
-                let coord = match rook_file {
-                    RookFile::AFile => TileCoord::new(7, 0),
-                    RookFile::HFile => TileCoord::new(7, 7),
-                };
-
-                //rook moved
-                if board.peek_tile(&coord).is_none() {
-                    return true;
-                }
-
-                // rook taken
-                if let Some(piece) = board.peek_tile(&coord) {
-                    if piece.color() != piece_color {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        // return rook not moved if none of above cases
-        false
-    }
-
-    fn is_king_check(piece_color: PieceColor, board: &Board) -> bool {
-        let king_coord = if piece_color == PieceColor::White {
-            TileCoord::new(0, 4)
-        } else {
-            TileCoord::new(7, 4)
-        };
-
-        let strategy =
-            StrategyBuilder::new_piece_strategy(PieceType::King, king_coord, piece_color, board);
-
-        if MoveValidator::is_check(strategy.color(), board) {
-            return true;
+    /// piece is moved if it is no longer on its start coord,
+    /// or was taken and replaced by another piece
+    fn is_moved(
+        piece_type: PieceType,
+        start_coord: TileCoord,
+        piece_color: PieceColor,
+        board: &Board,
+    ) -> bool {
+        match board.peek_tile(&start_coord) {
+            Some(piece) => piece.piece_type() != piece_type || piece.color() != piece_color,
+            None => true,
         }
-
-        false
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 pub enum KingCastleMoveResult {
     ShortCastle,
     LongCastle,
@@ -288,6 +334,16 @@ impl KingCastleState {
             h_file_rook_moved: false,
         }
     }
+
+    /// castle state with only the given castling rights left
+    pub fn from_rights(can_short_castle: bool, can_long_castle: bool) -> Self {
+        Self {
+            is_king_moved: !can_short_castle && !can_long_castle,
+            is_in_check: false,
+            a_file_rook_moved: !can_long_castle,
+            h_file_rook_moved: !can_short_castle,
+        }
+    }
 }
 
 impl Default for KingCastleState {
@@ -313,12 +369,40 @@ impl KingCastleValidator {
         }
     }
 
+    /// coord the king moves to when castling,
+    /// same for standard chess and chess960
+    pub fn castle_coord(piece_color: PieceColor, side: &KingCastleMoveResult) -> TileCoord {
+        match side {
+            KingCastleMoveResult::LongCastle => KingCastleValidator::long_castle_coord(piece_color),
+            KingCastleMoveResult::ShortCastle => {
+                KingCastleValidator::short_castle_coord(piece_color)
+            }
+        }
+    }
+
+    /// coord the rook moves to when castling,
+    /// same for standard chess and chess960
+    pub fn rook_castle_coord(piece_color: PieceColor, side: &KingCastleMoveResult) -> TileCoord {
+        let row = KingCastleValidator::back_row(piece_color);
+        match side {
+            KingCastleMoveResult::LongCastle => TileCoord::new(row, 3),
+            KingCastleMoveResult::ShortCastle => TileCoord::new(row, 5),
+        }
+    }
+
     pub fn king_start_coord(piece_color: PieceColor) -> TileCoord {
         match piece_color {
             PieceColor::White => TileCoord::new(0, 4),
             PieceColor::Black => TileCoord::new(7, 4),
         }
     }
+
+    pub fn back_row(piece_color: PieceColor) -> u8 {
+        match piece_color {
+            PieceColor::White => 0,
+            PieceColor::Black => 7,
+        }
+    }
 }
 
 // enum KingCastleType {
@@ -350,3 +434,58 @@ impl KingCastleValidator {
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::tile::TileCoord;
+
+    #[test]
+    pub fn test_castle_rights_persist() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+
+        // rooks move away and back to their start coords
+        for (old, new) in [
+            ((0, 7), (1, 7)),
+            ((7, 0), (6, 0)),
+            ((1, 7), (0, 7)),
+            ((6, 0), (7, 0)),
+        ] {
+            let (old, new) = (TileCoord::new(old.0, old.1), TileCoord::new(new.0, new.1));
+            assert!(board.move_piece(old, new, None).is_some());
+        }
+
+        assert_eq!("r3k2r/8/8/8/8/8/8/R3K2R w Qk - 4 3", board.to_fen());
+        assert!(board
+            .move_piece(TileCoord::new(0, 4), TileCoord::new(0, 6), None)
+            .is_none());
+
+        // rook taken on its start coord
+        let mut board = Board::from_fen(fen).unwrap();
+        assert!(board
+            .move_piece(TileCoord::new(0, 0), TileCoord::new(7, 0), None)
+            .is_some());
+        assert_eq!("R3k2r/8/8/8/8/8/8/4K2R b Kk - 0 1", board.to_fen());
+    }
+
+    #[test]
+    pub fn test_king_move_onto_rook_start_coord() {
+        // chess960 long castle right is gone, Kb1-a1 is a normal king move
+        let fen = "1k5r/8/8/8/8/8/8/1K5R w Hh - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        let move_result = board
+            .move_piece(TileCoord::new(0, 1), TileCoord::new(0, 0), None)
+            .unwrap();
+        assert!(!move_result.is_long_castle);
+        assert!(board.get_piece(&TileCoord::new(0, 7)).is_some());
+
+        // enemy piece on the rook start coord is taken
+        let fen = "1k5r/8/8/8/8/8/8/rK5R w Hh - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        let move_result = board
+            .move_piece(TileCoord::new(0, 1), TileCoord::new(0, 0), None)
+            .unwrap();
+        assert!(move_result.is_take && !move_result.is_long_castle);
+    }
+}
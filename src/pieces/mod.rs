@@ -1,3 +1,4 @@
+pub mod attack;
 pub mod bishop;
 pub mod king;
 pub mod knight;
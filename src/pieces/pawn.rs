@@ -1,38 +1,32 @@
 // use crate::console_log;
-use crate::board::Board;
 use crate::pieces::piece::{PieceColor, PieceType};
 use crate::pieces::strategy::PieceMoveStrategy;
-use crate::tile::{TileCoord, TileRank};
+use crate::square::{Direction, Square};
+use crate::tile::TileCoord;
 
 pub struct PawnMoveStrategy {
     pub color: PieceColor,
     pub coord: TileCoord,
     pub piece_type: PieceType,
-    pub board: *const Board,
 }
 
 impl PawnMoveStrategy {
     pub fn diagonal_moves(color: PieceColor, coord: TileCoord) -> Vec<TileCoord> {
-        let mut vec = vec![];
-        if color == PieceColor::White {
-            let left_diag_coord = TileCoord::new(coord.row() + 1, coord.col() - 1);
-            vec.push(left_diag_coord);
-
-            let right_diag_coord = TileCoord::new(coord.row() + 1, coord.col() + 1);
-            vec.push(right_diag_coord);
-        } else {
-            let left_diag_coord = TileCoord::new(coord.row() - 1, coord.col() + 1);
-            vec.push(left_diag_coord);
-
-            let right_diag_coord = TileCoord::new(coord.row() - 1, coord.col() - 1);
-
-            vec.push(right_diag_coord);
-        }
-
-        vec
+        let Ok(square) = Square::try_from(coord) else {
+            return vec![];
+        };
+        let (row_offset, _) = Direction::forward(color).offset();
+
+        [-1, 1]
+            .into_iter()
+            .filter_map(|col_offset| square.offset(row_offset, col_offset))
+            .map(TileCoord::from)
+            .collect()
     }
 
     /// Checks if is en passant take possible
+    /// en passant pawn can be on any rank, eg. horde pawns
+    /// double moving from the first rank
     pub fn is_en_passant_take(
         cur_coord: TileCoord,
         piece_color: PieceColor,
@@ -40,22 +34,32 @@ impl PawnMoveStrategy {
         last_en_passant_coord: Option<TileCoord>,
     ) -> bool {
         if let Some(en_passant_coord) = last_en_passant_coord {
-            if piece_color == PieceColor::White && cur_coord.rank() == TileRank::Rank5 {
-                return en_passant_coord.row() + 1 == new_coord.row()
-                    && en_passant_coord.col() == new_coord.col();
-            }
-            if piece_color == PieceColor::Black && cur_coord.rank() == TileRank::Rank4 {
-                return en_passant_coord.row() - 1 == new_coord.row()
-                    && en_passant_coord.col() == new_coord.col();
+            // en passant pawn must be next to the taking pawn
+            if en_passant_coord.row() != cur_coord.row()
+                || en_passant_coord.col().abs_diff(cur_coord.col()) != 1
+            {
+                return false;
             }
-            false
+
+            // taking pawn moves to the tile the en passant pawn passed over
+            let direction: i8 = match piece_color {
+                PieceColor::White => 1,
+                PieceColor::Black => -1,
+            };
+            en_passant_coord.row() as i8 + direction == new_coord.row() as i8
+                && en_passant_coord.col() == new_coord.col()
         } else {
             false
         }
     }
 
-    fn board(&self) -> &Board {
-        unsafe { self.board.as_ref().unwrap() }
+    /// pawns double move from their first or second rank,
+    /// first rank pawns are only found in horde or custom setups
+    pub fn is_double_move_row(piece_color: PieceColor, row: u8) -> bool {
+        match piece_color {
+            PieceColor::White => row <= 1,
+            PieceColor::Black => row >= 6,
+        }
     }
 }
 
@@ -73,83 +77,43 @@ impl PieceMoveStrategy for PawnMoveStrategy {
     }
 
     fn moves(&self) -> Vec<TileCoord> {
-        let mut valid_moves = PawnMoveStrategy::diagonal_moves(self.color(), self.coord());
-
-        // check if white pawn
-        if self.color == PieceColor::White {
-            // check if can take a piece diagonal
-            // if coord is not valid, peak at tile method
-            // will return None
-            for coord in PawnMoveStrategy::diagonal_moves(self.color, self.coord) {
-                let diag_piece = self.board().peek_tile(&coord);
-
-                if diag_piece.is_some() {
-                    valid_moves.push(coord)
+        // diagonal moves are only valid when taking, see `MoveValidator`
+        let mut valid_moves = PawnMoveStrategy::diagonal_moves(self.color, self.coord);
+        let Some(square) = self.square() else {
+            return valid_moves;
+        };
+        let forward = Direction::forward(self.color);
+
+        // single square move
+        if let Some(single) = square.step(forward) {
+            valid_moves.push(single.into());
+
+            // double move from the 1st or 2nd rank
+            if PawnMoveStrategy::is_double_move_row(self.color, square.row()) {
+                if let Some(double) = single.step(forward) {
+                    valid_moves.push(double.into());
                 }
             }
-
-            // check if white pawn on 2nd rank
-            if self.coord.rank() == TileRank::Rank2 {
-                let coord = TileCoord::new(TileRank::Rank4.into(), self.coord.col());
-                valid_moves.push(coord);
-            }
-
-            // single square move
-            let coord = TileCoord::new(self.coord.row() + 1, self.coord.col());
-            valid_moves.push(coord);
-        }
-
-        // check if white pawn on 2nd rank, add 2 moves ahead as valid move
-        if self.color == PieceColor::Black {
-            // check if can take a piece diagonal
-            // if coord is not valid, peak at tile method
-            // will return None
-            for coord in PawnMoveStrategy::diagonal_moves(self.color, self.coord) {
-                let diag_piece = self.board().peek_tile(&coord);
-
-                if diag_piece.is_some() {
-                    valid_moves.push(coord)
-                }
-            }
-
-            // double move on 7th rank
-            if self.coord.rank() == TileRank::Rank7 {
-                let coord = TileCoord::new(TileRank::Rank5.into(), self.coord.col());
-                valid_moves.push(coord);
-            }
-
-            // single square move
-            let coord = TileCoord::new(self.coord.row() - 1, self.coord.col());
-            valid_moves.push(coord);
         }
 
         valid_moves
     }
 
     /// returns all tiles between current tile coord
-    /// and new tile coord
+    /// and new tile coord, only double moves have a tile between
     fn tiles_between(&self, new_coord: TileCoord) -> Vec<TileCoord> {
-        let mut coords: Vec<TileCoord> = vec![];
-
-        let (cur_row, cur_col) = (self.coord().row(), self.coord().col());
+        let (Some(square), Ok(new_square)) = (self.square(), Square::try_from(new_coord)) else {
+            return vec![];
+        };
 
-        // no tiles between if diagonal move
-        if cur_col != new_coord.col() {
-            return coords;
-        }
-
-        // difference greater than to, means double pawn move, ie. first move
-        if cur_row.abs_diff(new_coord.row()) == 2 {
-            // check if negative row move
-            if (cur_row as i8 - new_coord.row() as i8) < 0 {
-                let new_coord = TileCoord::new(cur_row + 1, cur_col);
-                coords.push(new_coord);
-            } else {
-                let new_coord = TileCoord::new(cur_row - 1, cur_col);
-                coords.push(new_coord);
-            }
+        if square.col() != new_square.col() || square.row().abs_diff(new_square.row()) != 2 {
+            return vec![];
         }
 
-        coords
+        square
+            .between(new_square)
+            .into_iter()
+            .map(TileCoord::from)
+            .collect()
     }
 }
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 // use crate::console_log;
+use crate::error::ChessError;
 use crate::tile::TileCoord;
 
 #[wasm_bindgen]
@@ -50,6 +51,9 @@ impl Display for PieceType {
 #[wasm_bindgen]
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Piece {
+    /// stable id kept through moves, castling and promotion,
+    /// 0 if the piece was not placed on a board
+    id: u32,
     piece_type: PieceType,
     color: PieceColor,
     coord: TileCoord,
@@ -60,6 +64,7 @@ pub struct Piece {
 impl Piece {
     pub fn new(piece_type: PieceType, color: PieceColor, coord: TileCoord) -> Self {
         Self {
+            id: 0,
             piece_type,
             color,
             coord,
@@ -67,6 +72,11 @@ impl Piece {
         }
     }
 
+    /// stable id of the piece, used as key to render and animate pieces
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
     pub fn state(&self) -> PieceState {
         self.state.clone()
     }
@@ -94,20 +104,36 @@ impl Piece {
         self.state = new_state;
     }
 
-    pub fn to_bytes(&self) -> Vec<u8> {
-        bincode::serialize(&self).unwrap()
+    pub fn to_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        let bytes =
+            bincode::serialize(&self).map_err(|err| ChessError::Serialize(err.to_string()))?;
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Piece, JsValue> {
+        let piece = bincode::deserialize(&bytes).map_err(ChessError::from)?;
+        Ok(piece)
     }
 
-    pub fn from_bytes(bytes: Vec<u8>) -> Piece {
-        bincode::deserialize(&bytes).unwrap()
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        let json = serde_wasm_bindgen::to_value(&self)
+            .map_err(|err| ChessError::Serialize(err.to_string()))?;
+        Ok(json)
     }
 
-    pub fn to_json(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self).unwrap()
+    pub fn from_json(json: JsValue) -> Result<Piece, JsValue> {
+        let piece = serde_wasm_bindgen::from_value(json).map_err(ChessError::from)?;
+        Ok(piece)
+    }
+}
+
+impl Piece {
+    pub fn set_id(&mut self, id: u32) {
+        self.id = id
     }
 
-    pub fn from_json(json: JsValue) -> Piece {
-        serde_wasm_bindgen::from_value(json).unwrap()
+    pub fn set_coord(&mut self, coord: TileCoord) {
+        self.coord = coord
     }
 }
 
@@ -1,43 +1,22 @@
 // use crate::console_log;
-use crate::board::Board;
 use crate::pieces::piece::{PieceColor, PieceType};
-use crate::pieces::strategy::{PieceMoveStrategy, TilesBetween};
+use crate::pieces::strategy::PieceMoveStrategy;
+use crate::square::Direction;
 use crate::tile::TileCoord;
 
-pub enum RookFile {
-    AFile,
-    HFile,
-}
-
 pub struct RookMoveStrategy {
     pub color: PieceColor,
     pub coord: TileCoord,
     pub piece_type: PieceType,
-    pub board: *const Board,
 }
 
 impl PieceMoveStrategy for RookMoveStrategy {
     fn tiles_between(&self, new_coord: TileCoord) -> Vec<TileCoord> {
-        let moves = self.row_col_intersect();
-        let direction = self.move_direction(new_coord);
-
-        moves.tiles_between(direction, self.coord(), new_coord)
+        self.ray_tiles_between(new_coord, &Direction::STRAIGHT)
     }
 
     fn moves(&self) -> Vec<TileCoord> {
-        let mut moves_vec = vec![];
-
-        let moves = self.row_col_intersect();
-
-        for coord in moves.row {
-            moves_vec.push(coord);
-        }
-
-        for coord in moves.col {
-            moves_vec.push(coord);
-        }
-
-        moves_vec
+        self.ray_moves(&Direction::STRAIGHT)
     }
 
     fn coord(&self) -> TileCoord {
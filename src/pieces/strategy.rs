@@ -1,8 +1,11 @@
-use std::collections::HashSet;
+use std::fmt::Display;
+
+use wasm_bindgen::prelude::*;
 
 use crate::board::Board;
 // use crate::console_log;
 use crate::pieces::piece::{PieceColor, PieceType};
+use crate::square::{Direction, Square};
 use crate::tile::TileCoord;
 
 use crate::pieces::bishop::BishopMoveStrategy;
@@ -12,6 +15,7 @@ use crate::pieces::pawn::PawnMoveStrategy;
 use crate::pieces::queen::QueenMoveStrategy;
 use crate::pieces::rook::RookMoveStrategy;
 
+use super::attack::AttackValidator;
 use super::king::{KingCastleMoveResult, KingCastleValidator};
 
 pub struct MoveHandler<'a> {
@@ -43,51 +47,42 @@ impl<'a> MoveHandler<'a> {
         }
     }
 
+    /// moves both king and rook if the king move is a castle move
+    /// returns which side was castled, or None if not a castle move
     pub fn handle_king_castle_move(
         &mut self,
         piece_strategy: &dyn PieceMoveStrategy,
     ) -> Option<KingCastleMoveResult> {
         let piece_color = piece_strategy.color();
-        let rook_row = match piece_color {
-            PieceColor::White => 0,
-            PieceColor::Black => 7,
-        };
+        let mut castle_state = self.board.king_castle_state();
 
-        // get new rook coords
-        // tuple with following layout
-        // (old_col, new_col)
-        let rook_col = if self.new_coord == KingCastleValidator::long_castle_coord(piece_color) {
-            Some((0_u8, 3_u8))
-        } else if self.new_coord == KingCastleValidator::short_castle_coord(piece_color) {
-            Some((7_u8, 5_u8))
-        } else {
-            None
-        };
+        let side = castle_state.castle_side(
+            piece_color,
+            piece_strategy.coord(),
+            self.new_coord,
+            self.board,
+        )?;
 
-        if let Some(col) = rook_col {
-            // clear old rook coord
-            self.board
-                .set_new_tile(&TileCoord::new(rook_row, col.0), None, None);
+        // clear king and rook before placing either,
+        // in chess960 the king or rook may land on the others start coord
+        let rook_coord = castle_state.rook_start_coord(piece_color, &side);
+        let king = self.board.get_piece(&piece_strategy.coord())?;
+        let rook = self.board.get_piece(&rook_coord)?;
+        self.board.set_new_tile(&piece_strategy.coord(), None, None);
+        self.board.set_new_tile(&rook_coord, None, None);
 
-            // set new rook coord
-            self.board.set_new_tile(
-                &TileCoord::new(rook_row, col.1),
-                Some(PieceType::Rook),
-                Some(piece_color),
-            );
+        self.board
+            .place_piece(&KingCastleValidator::castle_coord(piece_color, &side), king);
+        self.board.place_piece(
+            &KingCastleValidator::rook_castle_coord(piece_color, &side),
+            rook,
+        );
 
-            // return whether it is a long castle or short castle
-            if self.new_coord == KingCastleValidator::long_castle_coord(piece_color) {
-                return Some(KingCastleMoveResult::LongCastle);
-            }
+        // in chess960 the king may castle without leaving its start coord
+        castle_state.king_state_mut(piece_color).is_king_moved = true;
+        self.board.set_king_castle_state(castle_state);
 
-            if self.new_coord == KingCastleValidator::short_castle_coord(piece_color) {
-                return Some(KingCastleMoveResult::ShortCastle);
-            }
-        };
-
-        // return no castling occurred
-        None
+        Some(side)
     }
 
     // ---
@@ -135,6 +130,52 @@ impl<'a> MoveHandler<'a> {
     }
 }
 
+/// Reason a move is invalid, used to explain invalid moves to the player
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidMoveReason {
+    OutOfBounds,
+    NoPiece,
+    NotYourTurn,
+    SameTile,
+    /// piece cannot move to the tile, eg. rook moving diagonally
+    InvalidPieceMove,
+    OwnPieceTake,
+    PathBlocked,
+    /// pawn can only move diagonally when taking
+    InvalidPawnTake,
+    /// king would be in check after the move
+    KingInCheck,
+    /// piece would expose its own king to the pinning piece
+    PiecePinned,
+    /// king or castling rook has moved or the rook was taken
+    CastleRightsLost,
+    /// king would pass over or land on an attacked tile
+    CastleThroughCheck,
+    /// move is not allowed by the variant, eg. compulsory take in antichess
+    VariantRule,
+}
+
+impl Display for InvalidMoveReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "tile is not on the board"),
+            Self::NoPiece => write!(f, "there is no piece on the tile"),
+            Self::NotYourTurn => write!(f, "it is not your turn"),
+            Self::SameTile => write!(f, "piece must move to another tile"),
+            Self::InvalidPieceMove => write!(f, "piece cannot move like that"),
+            Self::OwnPieceTake => write!(f, "cannot take your own piece"),
+            Self::PathBlocked => write!(f, "another piece is in the way"),
+            Self::InvalidPawnTake => write!(f, "pawns can only move diagonally to take"),
+            Self::KingInCheck => write!(f, "your king would be in check"),
+            Self::PiecePinned => write!(f, "piece is pinned to your king"),
+            Self::CastleRightsLost => write!(f, "king or rook has already moved"),
+            Self::CastleThroughCheck => write!(f, "king cannot castle through check"),
+            Self::VariantRule => write!(f, "move is not allowed in this variant"),
+        }
+    }
+}
+
 pub struct MoveValidator<'a> {
     new_coord: TileCoord,
     board: &'a Board,
@@ -154,70 +195,185 @@ impl<'a> MoveValidator<'a> {
         piece_strategy: &dyn PieceMoveStrategy,
         ignore_check: bool,
     ) -> bool {
+        self.validate_move(piece_strategy, ignore_check).is_ok()
+    }
+
+    /// same as `is_valid_move`, returns reason the move is invalid
+    pub fn validate_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+        ignore_check: bool,
+    ) -> Result<(), InvalidMoveReason> {
         // if new coord not in bounds
         // is invalid move
         if !self.new_coord.in_bounds() {
-            return false;
+            return Err(InvalidMoveReason::OutOfBounds);
+        }
+
+        // king castle move validation
+        if let Some(side) = self.castle_side(piece_strategy) {
+            return self.validate_king_castle_move(piece_strategy, &side, ignore_check);
         }
+
         // get possible piece moves based on piece_strategy
         let possible_moves = piece_strategy.moves();
 
         // check if piece between cur_coord and new_coord
         if self.is_blocking_piece(&possible_moves, piece_strategy) {
-            return false;
+            return Err(InvalidMoveReason::PathBlocked);
         }
 
         // pawn specific move validation
-        if piece_strategy.piece_type() == PieceType::Pawn
-            && !self.is_valid_pawn_move(piece_strategy)
-        {
-            return false;
-        }
-
-        // // king castle move validation
-        if piece_strategy.piece_type() == PieceType::King
-            && !self.is_valid_king_castle_move(piece_strategy)
-        {
-            return false;
+        if piece_strategy.piece_type() == PieceType::Pawn {
+            self.validate_pawn_move(piece_strategy)?;
         }
 
         // check if trying to move to same square as current
         if self.is_same_coord_move(piece_strategy) {
-            return false;
+            return Err(InvalidMoveReason::SameTile);
         }
 
         // check if own piece take
         if self.is_own_piece_take(piece_strategy) {
-            return false;
+            return Err(InvalidMoveReason::OwnPieceTake);
         }
 
         // handle rest piece moves
         if !possible_moves.contains(&self.new_coord) {
-            return false;
+            return Err(InvalidMoveReason::InvalidPieceMove);
+        }
+
+        // variant specific move validation
+        let variant_rules = self.board.variant_rules();
+        if !variant_rules.is_valid_move(piece_strategy, self, self.board) {
+            return Err(InvalidMoveReason::VariantRule);
         }
 
         // flag used to validate if king is in check,
         // possible move out of check
         // possible move into check
         if !ignore_check {
-            // cant move into check
+            // game is won, eg. exploding enemy king in atomic
+            if variant_rules.is_winning_move(piece_strategy, self, self.board) {
+                return Ok(());
+            }
+
+            // king is an ordinary piece, ie. antichess
+            if !variant_rules.has_check() {
+                return Ok(());
+            }
+
+            // check and pins follow from piece attacks,
+            // validated without copying the board
+            if variant_rules.has_pins() {
+                return self.validate_legal_move(piece_strategy);
+            }
+
+            // cant move into or stay in check
             if MoveValidator::is_possible_check(piece_strategy, self.board, self.new_coord) {
-                return false;
+                return Err(InvalidMoveReason::KingInCheck);
             }
+        }
 
-            // check if currently in check and possible move out of check
-            if MoveValidator::is_check(piece_strategy.color(), self.board)
-                && !MoveValidator::is_possible_check(piece_strategy, self.board, self.new_coord)
-            {
-                return true;
+        Ok(())
+    }
+
+    /// validate dropping piece from pocket on new coord
+    pub fn is_valid_drop(&self, piece_type: PieceType, piece_color: PieceColor) -> bool {
+        if !self.new_coord.in_bounds() || !self.board.variant_rules().has_pockets() {
+            return false;
+        }
+
+        if self.board.pocket(piece_color).count(piece_type) == 0 {
+            return false;
+        }
+
+        // can only drop on empty tile
+        if self.board.peek_tile(&self.new_coord).is_some() {
+            return false;
+        }
+
+        // pawns cannot be dropped on first or last rank
+        if piece_type == PieceType::Pawn && (self.new_coord.row() == 0 || self.new_coord.row() == 7)
+        {
+            return false;
+        }
+
+        // drop cannot leave king in check, ie. must block check if in check
+        let mut board_copy = self.board.clone();
+        board_copy.set_new_tile(&self.new_coord, Some(piece_type), Some(piece_color));
+        !MoveValidator::is_check(piece_color, &board_copy)
+    }
+
+    pub fn new_coord(&self) -> TileCoord {
+        self.new_coord
+    }
+
+    /// validate move does not leave own king in check,
+    /// king cannot move to an attacked tile, in double check only the king can move,
+    /// in check the checking piece must be taken or blocked and
+    /// pinned pieces can only move along the line of the pin
+    fn validate_legal_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+    ) -> Result<(), InvalidMoveReason> {
+        let piece_color = piece_strategy.color();
+        let enemy_color = PieceColor::opposite_color(piece_color);
+
+        // no king to leave in check, eg. white in horde
+        let Some(king_coord) = AttackValidator::king_coord(piece_color, self.board) else {
+            return Ok(());
+        };
+
+        // king is removed so it does not block attacks along its own line
+        if piece_strategy.piece_type() == PieceType::King {
+            if AttackValidator::is_attacked_without(
+                self.new_coord,
+                enemy_color,
+                self.board,
+                &[piece_strategy.coord()],
+            ) {
+                return Err(InvalidMoveReason::KingInCheck);
+            }
+            return Ok(());
+        }
+
+        // en passant removes two pieces from the board,
+        // rare enough to validate on a board copy
+        if self.is_en_passant_take(piece_strategy) {
+            if MoveValidator::is_possible_check(piece_strategy, self.board, self.new_coord) {
+                return Err(InvalidMoveReason::KingInCheck);
+            }
+            return Ok(());
+        }
+
+        let checkers = AttackValidator::attackers_of(king_coord, enemy_color, self.board);
+        match checkers.as_slice() {
+            [] => {}
+            [checker] => {
+                // take the checking piece or block the check
+                if self.new_coord != *checker
+                    && !AttackValidator::tiles_between(king_coord, *checker)
+                        .contains(&self.new_coord)
+                {
+                    return Err(InvalidMoveReason::KingInCheck);
+                }
             }
+            // double check, only the king can move
+            _ => return Err(InvalidMoveReason::KingInCheck),
+        }
 
-            if MoveValidator::is_check(piece_strategy.color(), self.board) {
-                return false;
+        if let Some(pinner) =
+            AttackValidator::pinned_by(piece_strategy.coord(), king_coord, self.board)
+        {
+            if self.new_coord != pinner
+                && !AttackValidator::tiles_between(king_coord, pinner).contains(&self.new_coord)
+            {
+                return Err(InvalidMoveReason::PiecePinned);
             }
         }
 
-        true
+        Ok(())
     }
 
     /// check if piece at new coord
@@ -225,9 +381,33 @@ impl<'a> MoveValidator<'a> {
         self.board.get_piece(&self.new_coord).is_some()
     }
 
+    /// returns castle side if king move is a castle move
+    pub fn castle_side(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+    ) -> Option<KingCastleMoveResult> {
+        if piece_strategy.piece_type() != PieceType::King
+            || !self.board.variant_rules().has_castling()
+        {
+            return None;
+        }
+
+        self.board.king_castle_state().castle_side(
+            piece_strategy.color(),
+            piece_strategy.coord(),
+            self.new_coord,
+            self.board,
+        )
+    }
+
     /// check if trying to take king
     /// cannot take king off the board
+    /// unless the variant has no check, ie. antichess
     pub fn is_king_take(&self) -> bool {
+        if !self.board.variant_rules().has_check() {
+            return false;
+        }
+
         let new_piece_at_coord = self.board.peek_tile(&self.new_coord);
         if let Some(piece) = new_piece_at_coord {
             if piece.piece_type() == PieceType::King {
@@ -238,55 +418,104 @@ impl<'a> MoveValidator<'a> {
     }
 
     // validate king castle move
-    fn is_valid_king_castle_move(&self, piece_strategy: &dyn PieceMoveStrategy) -> bool {
+    fn validate_king_castle_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+        side: &KingCastleMoveResult,
+        ignore_check: bool,
+    ) -> Result<(), InvalidMoveReason> {
         // `board.king_castle_state()` method returns clone of current state
         let board_king_castle_state = self.board.king_castle_state();
         let piece_color = piece_strategy.color();
 
-        let king_castle_state = match piece_color {
-            PieceColor::White => &board_king_castle_state.white_king,
-            PieceColor::Black => &board_king_castle_state.black_king,
-        };
+        // castling rook must still be on its start coord,
+        // being in check is validated with the attacked tiles below
+        if !board_king_castle_state.has_castle_rook(piece_color, side, self.board) {
+            return Err(InvalidMoveReason::CastleRightsLost);
+        }
+
+        let king_coord = piece_strategy.coord();
+        let rook_coord = board_king_castle_state.rook_start_coord(piece_color, side);
+
+        // all tiles the king and rook move over, including their
+        // target coords, must be empty apart from the king and rook
+        let king_target = KingCastleValidator::castle_coord(piece_color, side);
+        let rook_target = KingCastleValidator::rook_castle_coord(piece_color, side);
+
+        for (from_col, to_col) in [
+            (king_coord.col(), king_target.col()),
+            (rook_coord.col(), rook_target.col()),
+        ] {
+            for col in from_col.min(to_col)..=from_col.max(to_col) {
+                let coord = TileCoord::new(king_coord.row(), col);
+                if coord != king_coord
+                    && coord != rook_coord
+                    && self.board.peek_tile(&coord).is_some()
+                {
+                    return Err(InvalidMoveReason::PathBlocked);
+                }
+            }
+        }
 
-        if self.new_coord == KingCastleValidator::short_castle_coord(piece_color)
-            && (king_castle_state.is_king_moved
-                || king_castle_state.h_file_rook_moved
-                || king_castle_state.is_in_check)
-        {
-            return false;
+        // king cannot pass over or land on an attacked tile,
+        // king and rook are removed so they do not block any attacks
+        if !ignore_check {
+            let enemy_color = PieceColor::opposite_color(piece_color);
+            let (from_col, to_col) = (king_coord.col(), king_target.col());
+
+            for col in from_col.min(to_col)..=from_col.max(to_col) {
+                let coord = TileCoord::new(king_coord.row(), col);
+                if AttackValidator::is_attacked_without(
+                    coord,
+                    enemy_color,
+                    self.board,
+                    &[king_coord, rook_coord],
+                ) {
+                    // king cannot castle out of check
+                    if coord == king_coord {
+                        return Err(InvalidMoveReason::KingInCheck);
+                    }
+                    return Err(InvalidMoveReason::CastleThroughCheck);
+                }
+            }
         }
-        if self.new_coord == KingCastleValidator::long_castle_coord(piece_color)
-            && (king_castle_state.is_king_moved
-                || king_castle_state.a_file_rook_moved
-                || king_castle_state.is_in_check)
+
+        // cant castle into check, the attacked tiles
+        // above already include the king target with pins
+        if !ignore_check
+            && !self.board.variant_rules().has_pins()
+            && MoveValidator::is_possible_check(piece_strategy, self.board, self.new_coord)
         {
-            return false;
+            return Err(InvalidMoveReason::CastleThroughCheck);
         }
 
-        true
+        Ok(())
     }
 
     // validate pawn move
-    fn is_valid_pawn_move(&self, piece_strategy: &dyn PieceMoveStrategy) -> bool {
+    fn validate_pawn_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+    ) -> Result<(), InvalidMoveReason> {
         let diagonal_moves =
             PawnMoveStrategy::diagonal_moves(piece_strategy.color(), piece_strategy.coord());
         let is_diagonal = diagonal_moves.contains(&self.new_coord);
 
         // check if can take en passant
         if self.is_en_passant_take(piece_strategy) {
-            return true;
+            return Ok(());
         }
 
         if is_diagonal && !self.is_take() {
-            return false;
+            return Err(InvalidMoveReason::InvalidPawnTake);
         }
 
         // return not valid move if not diagonal and taking
         if !is_diagonal && self.is_take() {
-            return false;
+            return Err(InvalidMoveReason::PathBlocked);
         }
 
-        true
+        Ok(())
     }
 
     /// check if own piece at new coord
@@ -353,6 +582,16 @@ impl<'a> MoveValidator<'a> {
 
     /// main method to validate whether king is in check
     pub fn is_check(piece_color: PieceColor, board: &Board) -> bool {
+        // king is an ordinary piece in variants without check
+        if !board.variant_rules().has_check() {
+            return false;
+        }
+
+        // king is in check if attacked by any enemy piece
+        if board.variant_rules().has_pins() {
+            return !AttackValidator::checkers(piece_color, board).is_empty();
+        }
+
         let enemy_piece_tiles = MoveHandler::enemy_piece_coords(piece_color, board);
 
         for coord in enemy_piece_tiles {
@@ -420,6 +659,15 @@ impl<'a> MoveValidator<'a> {
 
     /// possibility of checkmate
     pub fn is_checkmate(piece_color: PieceColor, board: &Board) -> bool {
+        if !board.variant_rules().has_check() {
+            return false;
+        }
+
+        !MoveValidator::has_valid_move(piece_color, board)
+    }
+
+    /// check if any piece of piece color has a valid move
+    pub fn has_valid_move(piece_color: PieceColor, board: &Board) -> bool {
         let own_pieces = MoveHandler::own_piece_coords(piece_color, board);
         // create new validator based on current board
 
@@ -440,17 +688,16 @@ impl<'a> MoveValidator<'a> {
                 if new_coord.in_bounds() {
                     let move_validator = MoveValidator::new(new_coord, board);
 
-                    // check if any valid move and can move out of check
+                    // check if any valid move, ie. can move out of check
                     if move_validator.is_valid_move(own_piece_strategy.as_ref(), false) {
-                        // return false, ie. not checkmate
-                        return false;
+                        return true;
                     }
                 }
             }
         }
 
-        // if not possible moves, then is checkmate
-        true
+        // no possible moves
+        false
     }
 }
 
@@ -471,338 +718,40 @@ pub trait PieceMoveStrategy {
         self.tiles_between(new_coord).len() + 1
     }
 
-    fn move_direction(&self, new_coord: TileCoord) -> MoveDirection {
-        let (cur_row, cur_col) = self.coord().row_col();
-        let (new_row, new_col) = new_coord.row_col();
-
-        if new_row > cur_row {
-            return MoveDirection::Up;
-        }
-
-        if new_row < cur_row {
-            return MoveDirection::Down;
-        }
-
-        if new_col > cur_col {
-            return MoveDirection::Right;
-        }
-
-        if new_col < cur_col {
-            return MoveDirection::Left;
-        }
-
-        MoveDirection::None
-    }
-
-    fn all_rows(&self) -> Vec<Vec<TileCoord>> {
-        let mut all_rows = vec![];
-        for row in 0..8 {
-            let rows: Vec<TileCoord> = (0..8).map(|col| TileCoord::new(row, col)).collect();
-            all_rows.push(rows);
-        }
-        all_rows
-    }
-
-    fn all_cols(&self) -> Vec<Vec<TileCoord>> {
-        let mut all_cols = vec![];
-
-        for col in 0..8 {
-            let cols: Vec<TileCoord> = (0..8).map(|row| TileCoord::new(row, col)).collect();
-            all_cols.push(cols);
-        }
-
-        all_cols
-    }
-
-    fn all_left_diag(&self) -> Vec<Vec<TileCoord>> {
-        let mut all_left_diags: Vec<Vec<TileCoord>> = vec![];
-
-        for d in 0..16 {
-            let mut cur_diag = vec![];
-            // 0 if d < M else d - M + 1
-            let mut row = if d < 8 { 0 } else { d - 8 + 1 };
-
-            // d if d < M else M - 1
-            let mut col = if d < 8 { d } else { 7 };
-
-            // while r < N and c > -1:
-            // intermediate.append(matrix[r][c])
-            // r += 1
-            // c -= 1
-            while row < 8 && col as i8 > -1 {
-                cur_diag.push(TileCoord::new(row, col));
-                row += 1;
-                col -= 1;
-            }
-            all_left_diags.push(cur_diag);
-        }
-
-        all_left_diags
-    }
-
-    fn all_right_diag(&self) -> Vec<Vec<TileCoord>> {
-        let mut all_right_diags: Vec<Vec<TileCoord>> = vec![];
-
-        for d in 0..16 {
-            let mut cur_diag = vec![];
-            // 0 if d < M else d - M + 1
-            let mut row = if d < 8 { 7 } else { 7 - (d - 7) };
-
-            // d if d < M else M - 1
-            let mut col = if d < 8 { d } else { 7 };
-
-            // while r < N and c > -1:
-            // intermediate.append(matrix[r][c])
-            // r += 1
-            // c -= 1
-            while row as i8 > -1 && col as i8 > -1 {
-                cur_diag.push(TileCoord::new(row, col));
-                row -= 1;
-                col -= 1;
-            }
-            all_right_diags.push(cur_diag);
-        }
-
-        all_right_diags
-    }
-
-    fn row_intersect(&self) -> Vec<TileCoord> {
-        // loop over all rows on board
-        for _row in self.all_rows() {
-            let mut row_moves: HashSet<TileCoord> = HashSet::new();
-
-            // check if current coord in row
-            if _row.contains(&self.coord()) {
-                for tile in _row {
-                    row_moves.insert(tile);
-                }
-
-                // remove self from possible moves
-                row_moves.remove(&self.coord());
-
-                return row_moves.into_iter().collect();
-            }
-        }
-
-        // is empty
-        vec![]
-    }
-
-    fn col_intersect(&self) -> Vec<TileCoord> {
-        // loop over all cols on board
-        for _col in self.all_cols() {
-            let mut col_moves: HashSet<TileCoord> = HashSet::new();
-
-            // if current coord in col
-            if _col.contains(&self.coord()) {
-                // add possible moves
-                for tile in _col {
-                    col_moves.insert(tile);
-                }
-
-                // remove self
-                col_moves.remove(&self.coord());
-
-                return col_moves.into_iter().collect();
-            }
-        }
-
-        // is empty
-        vec![]
-    }
-
-    fn left_diag_intersect(&self) -> Vec<TileCoord> {
-        for diag in self.all_left_diag() {
-            let mut diag_moves: HashSet<TileCoord> = HashSet::new();
-
-            if diag.contains(&self.coord()) {
-                for tile in diag {
-                    diag_moves.insert(tile);
-                }
-
-                // remove self
-                diag_moves.remove(&self.coord());
-
-                return diag_moves.into_iter().collect();
-            }
-        }
-
-        // is empty
-        vec![]
+    /// square of the piece, None if the piece coord is off the board
+    fn square(&self) -> Option<Square> {
+        Square::try_from(self.coord()).ok()
     }
 
-    fn right_diag_intersect(&self) -> Vec<TileCoord> {
-        for diag in self.all_right_diag() {
-            let mut diag_moves: HashSet<TileCoord> = HashSet::new();
-
-            if diag.contains(&self.coord()) {
-                for tile in diag {
-                    diag_moves.insert(tile);
-                }
-
-                // remove self
-                diag_moves.remove(&self.coord());
-
-                return diag_moves.into_iter().collect();
-            }
-        }
-
-        // is empty
-        vec![]
-    }
-
-    fn row_col_intersect(&self) -> StraightMoves {
-        StraightMoves {
-            row: self.row_intersect(),
-            col: self.col_intersect(),
-        }
-    }
-
-    fn diag_intersect(&self) -> DiagMoves {
-        DiagMoves {
-            left_diag: self.left_diag_intersect(),
-            right_diag: self.right_diag_intersect(),
-        }
-    }
-}
-
-#[derive(Debug)]
-pub enum MoveDirection {
-    Up,
-    Down,
-    Left,
-    Right,
-    None,
-}
-
-pub trait TilesBetween {
-    /// returns all tiles between current tile coord
-    /// and new tile coord
-    fn tiles_between(
-        &self,
-        direction: MoveDirection,
-        cur_coord: TileCoord,
-        new_coord: TileCoord,
-    ) -> Vec<TileCoord>;
-}
-
-#[derive(Debug)]
-pub struct DiagMoves {
-    pub left_diag: Vec<TileCoord>,
-    pub right_diag: Vec<TileCoord>,
-}
-
-impl TilesBetween for DiagMoves {
-    fn tiles_between(
-        &self,
-        direction: MoveDirection,
-        cur_coord: TileCoord,
-        new_coord: TileCoord,
-    ) -> Vec<TileCoord> {
-        let mut coords: Vec<TileCoord> = vec![];
-        let (cur_row, cur_col) = (cur_coord.row(), cur_coord.col());
-        let (new_row, new_col) = (new_coord.row(), new_coord.col());
-
-        match direction {
-            MoveDirection::Up => {
-                // use right diags
-                if new_col > cur_col {
-                    for coord in &self.right_diag {
-                        // only add higher rows to tiles between
-                        if coord.row() > cur_row && coord.row() < new_row {
-                            coords.push(*coord);
-                        }
-                    }
-                } else {
-                    // use left diags
-                    for coord in &self.left_diag {
-                        // only add higher rows to tiles between
-                        if coord.row() > cur_row && coord.row() < new_row {
-                            coords.push(*coord);
-                        }
-                    }
-                }
-            }
-            MoveDirection::Down => {
-                // use left diags
-                if new_col > cur_col {
-                    for coord in &self.left_diag {
-                        // only add higher rows to tiles between
-                        if coord.row() < cur_row && coord.row() > new_row {
-                            coords.push(*coord);
-                        }
-                    }
-                } else {
-                    // use right diags
-                    for coord in &self.right_diag {
-                        // only add higher rows to tiles between
-                        if coord.row() < cur_row && coord.row() > new_row {
-                            coords.push(*coord);
-                        }
-                    }
-                }
-            }
-            _ => (),
-        }
+    /// all tiles along directions up to the edge of the board,
+    /// used by sliding pieces, ie. rook, bishop and queen
+    fn ray_moves(&self, directions: &[Direction]) -> Vec<TileCoord> {
+        let Some(square) = self.square() else {
+            return vec![];
+        };
 
-        coords
+        directions
+            .iter()
+            .flat_map(|direction| square.ray(*direction))
+            .map(TileCoord::from)
+            .collect()
     }
-}
 
-#[derive(Debug)]
-pub struct StraightMoves {
-    pub row: Vec<TileCoord>,
-    pub col: Vec<TileCoord>,
-}
+    /// tiles between piece and new coord along one of directions,
+    /// empty if new coord is not in any of the directions
+    fn ray_tiles_between(&self, new_coord: TileCoord, directions: &[Direction]) -> Vec<TileCoord> {
+        let (Some(square), Ok(new_square)) = (self.square(), Square::try_from(new_coord)) else {
+            return vec![];
+        };
 
-impl TilesBetween for StraightMoves {
-    fn tiles_between(
-        &self,
-        direction: MoveDirection,
-        cur_coord: TileCoord,
-        new_coord: TileCoord,
-    ) -> Vec<TileCoord> {
-        let mut coords: Vec<TileCoord> = vec![];
-        let (cur_row, cur_col) = (cur_coord.row(), cur_coord.col());
-        let (new_row, new_col) = (new_coord.row(), new_coord.col());
-
-        match direction {
-            MoveDirection::Up => {
-                for coord in &self.col {
-                    // only add higher rows to tiles between
-                    if coord.row() > cur_row && coord.row() < new_row {
-                        coords.push(*coord);
-                    }
-                }
-            }
-            MoveDirection::Right => {
-                for coord in &self.row {
-                    // only add higher cols to tiles between
-                    if coord.col() > cur_col && coord.col() < new_col {
-                        coords.push(*coord);
-                    }
-                }
-            }
-            MoveDirection::Down => {
-                for coord in &self.col {
-                    // only add lower rows to tiles between
-                    if coord.row() < cur_row && coord.row() > new_row {
-                        coords.push(*coord);
-                    }
-                }
-            }
-            MoveDirection::Left => {
-                for coord in &self.row {
-                    // only add lower cols to tiles between
-                    if coord.col() < cur_col && coord.col() > new_col {
-                        coords.push(*coord);
-                    }
-                }
-            }
-            MoveDirection::None => (),
+        match square.direction_to(new_square) {
+            Some(direction) if directions.contains(&direction) => square
+                .between(new_square)
+                .into_iter()
+                .map(TileCoord::from)
+                .collect(),
+            _ => vec![],
         }
-
-        coords
     }
 }
 
@@ -819,25 +768,21 @@ impl StrategyBuilder {
                 color: piece_color,
                 piece_type,
                 coord,
-                board,
             }),
             PieceType::Rook => Box::new(RookMoveStrategy {
                 color: piece_color,
                 piece_type,
                 coord,
-                board,
             }),
             PieceType::Bishop => Box::new(BishopMoveStrategy {
                 color: piece_color,
                 piece_type,
                 coord,
-                board,
             }),
             PieceType::Knight => Box::new(KnightMoveStrategy {
                 color: piece_color,
                 piece_type,
                 coord,
-                board,
             }),
             PieceType::King => Box::new(KingMoveStrategy {
                 color: piece_color,
@@ -849,7 +794,6 @@ impl StrategyBuilder {
                 color: piece_color,
                 piece_type,
                 coord,
-                board,
             }),
         }
     }
@@ -32,3 +32,63 @@ pub fn get_piece_default(row: u8, col: u8) -> Option<Piece> {
         _ => None,
     }
 }
+
+/// back rank piece types for chess960 start position index 0..960
+/// uses Scharnagl numbering, index 518 is the standard start position
+pub fn get_chess960_back_rank(index: u16) -> Option<[PieceType; 8]> {
+    if index >= 960 {
+        return None;
+    }
+
+    let mut back_rank: [Option<PieceType>; 8] = [None; 8];
+    let mut n = index as usize;
+
+    // light squared bishop on b, d, f or h file
+    back_rank[(n % 4) * 2 + 1] = Some(PieceType::Bishop);
+    n /= 4;
+
+    // dark squared bishop on a, c, e or g file
+    back_rank[(n % 4) * 2] = Some(PieceType::Bishop);
+    n /= 4;
+
+    // queen on nth empty file
+    let queen_idx = n % 6;
+    n /= 6;
+    place_on_empty(&mut back_rank, queen_idx, PieceType::Queen);
+
+    // knights on remaining 5 empty files
+    let (knight_1, knight_2) = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+    ][n];
+    // place second knight first so first knight index is unchanged
+    place_on_empty(&mut back_rank, knight_2, PieceType::Knight);
+    place_on_empty(&mut back_rank, knight_1, PieceType::Knight);
+
+    // rook, king, rook on the last 3 empty files
+    place_on_empty(&mut back_rank, 0, PieceType::Rook);
+    place_on_empty(&mut back_rank, 0, PieceType::King);
+    place_on_empty(&mut back_rank, 0, PieceType::Rook);
+
+    // SAFETY:
+    // all 8 files are filled above
+    Some(back_rank.map(|piece_type| piece_type.unwrap()))
+}
+
+fn place_on_empty(back_rank: &mut [Option<PieceType>; 8], empty_idx: usize, piece_type: PieceType) {
+    if let Some(file) = back_rank
+        .iter_mut()
+        .filter(|piece_type| piece_type.is_none())
+        .nth(empty_idx)
+    {
+        *file = Some(piece_type);
+    }
+}
@@ -0,0 +1,304 @@
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::parser::MoveResult;
+use crate::pieces::piece::PieceType;
+use crate::pieces::strategy::InvalidMoveReason;
+use crate::tile::{TileCoord, TileState};
+
+/// Action resulting from a selection event, see `SelectionController`
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionAction {
+    /// piece selected and its moves highlighted
+    Select,
+    /// other piece of the same color selected
+    Reselect,
+    /// selection cleared
+    Deselect,
+    /// selected piece moved, see `SelectionResult::move_result`
+    Move,
+    /// pawn moved to the last rank, call `SelectionController::promote`
+    NeedsPromotion,
+    /// move or selection is not allowed, see `SelectionResult::reason`
+    Illegal,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct SelectionResult {
+    pub action: SelectionAction,
+    /// selected coord after the event
+    pub selected: Option<TileCoord>,
+    /// reason the event is illegal
+    pub reason: Option<InvalidMoveReason>,
+    #[wasm_bindgen(skip)]
+    pub move_result: Option<MoveResult>,
+}
+
+#[wasm_bindgen]
+impl SelectionResult {
+    /// result of the move made by the event
+    pub fn move_result(&self) -> Option<MoveResult> {
+        self.move_result.clone()
+    }
+}
+
+impl SelectionResult {
+    fn new(action: SelectionAction, selected: Option<TileCoord>) -> Self {
+        Self {
+            action,
+            selected,
+            reason: None,
+            move_result: None,
+        }
+    }
+
+    fn illegal(reason: InvalidMoveReason) -> Self {
+        Self {
+            reason: Some(reason),
+            ..SelectionResult::new(SelectionAction::Illegal, None)
+        }
+    }
+}
+
+/// Click and drag to move interaction, turns square clicked, drag start
+/// and drop events into a `SelectionAction`, the active tile and move
+/// highlights on the board are updated to match the selection
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct SelectionController {
+    selected: Option<TileCoord>,
+    /// from and to coord of the move waiting for a promote piece
+    pending_promotion: Option<(TileCoord, TileCoord)>,
+}
+
+#[wasm_bindgen]
+impl SelectionController {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn selected(&self) -> Option<TileCoord> {
+        self.selected
+    }
+
+    pub fn is_promotion_pending(&self) -> bool {
+        self.pending_promotion.is_some()
+    }
+
+    /// square clicked, selects the piece on coord or moves
+    /// the selected piece to coord
+    pub fn click(&mut self, coord: TileCoord, board: &mut Board) -> SelectionResult {
+        self.pending_promotion = None;
+
+        match self.selected {
+            Some(selected) if selected == coord => self.deselect(board),
+            Some(selected) => self.move_selected(selected, coord, board),
+            None => self.select(coord, board),
+        }
+    }
+
+    /// piece dragged from coord, always selects the dragged piece
+    pub fn drag_start(&mut self, coord: TileCoord, board: &mut Board) -> SelectionResult {
+        self.pending_promotion = None;
+
+        if self.selected == Some(coord) {
+            return SelectionResult::new(SelectionAction::Select, Some(coord));
+        }
+        self.select(coord, board)
+    }
+
+    /// dragged piece dropped on coord, dropping back on the
+    /// dragged tile keeps the piece selected
+    pub fn drop(&mut self, coord: TileCoord, board: &mut Board) -> SelectionResult {
+        match self.selected {
+            Some(selected) if selected == coord => {
+                SelectionResult::new(SelectionAction::Select, Some(coord))
+            }
+            Some(selected) => self.move_selected(selected, coord, board),
+            None => self.deselect(board),
+        }
+    }
+
+    /// complete the move waiting for a promote piece
+    pub fn promote(&mut self, piece_type: PieceType, board: &mut Board) -> SelectionResult {
+        let Some((from_coord, to_coord)) = self.pending_promotion.take() else {
+            return self.deselect(board);
+        };
+
+        let move_result = board.move_piece(from_coord, to_coord, Some(piece_type));
+        self.clear(board);
+
+        match move_result {
+            Some(move_result) => SelectionResult {
+                move_result: Some(move_result),
+                ..SelectionResult::new(SelectionAction::Move, None)
+            },
+            None => SelectionResult::illegal(InvalidMoveReason::VariantRule),
+        }
+    }
+
+    /// clear selection and any pending promotion
+    pub fn deselect(&mut self, board: &mut Board) -> SelectionResult {
+        self.pending_promotion = None;
+        self.clear(board);
+        SelectionResult::new(SelectionAction::Deselect, None)
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    fn select(&mut self, coord: TileCoord, board: &mut Board) -> SelectionResult {
+        if !coord.in_bounds() {
+            self.clear(board);
+            return SelectionResult::illegal(InvalidMoveReason::OutOfBounds);
+        }
+
+        let piece_color = match board.get_piece(&coord) {
+            Some(piece) => piece.color(),
+            None => return self.deselect(board),
+        };
+
+        if piece_color != board.active_color() {
+            self.clear(board);
+            return SelectionResult::illegal(InvalidMoveReason::NotYourTurn);
+        }
+
+        let action = match self.selected {
+            Some(_) => SelectionAction::Reselect,
+            None => SelectionAction::Select,
+        };
+
+        self.clear(board);
+        // SAFETY: coord checked in bounds above
+        board.set_tile_state(&coord, TileState::Active).unwrap();
+        board.highlight_moves(coord);
+        self.selected = Some(coord);
+
+        SelectionResult::new(action, Some(coord))
+    }
+
+    fn move_selected(
+        &mut self,
+        from_coord: TileCoord,
+        to_coord: TileCoord,
+        board: &mut Board,
+    ) -> SelectionResult {
+        if let Some(reason) = board.explain_move(from_coord, to_coord) {
+            // clicking another own piece selects it,
+            // checked after the move so chess960 king to rook castles
+            let is_own_piece = board
+                .get_piece(&to_coord)
+                .is_some_and(|piece| piece.color() == board.active_color());
+
+            if is_own_piece {
+                return self.select(to_coord, board);
+            }
+
+            self.clear(board);
+            return SelectionResult::illegal(reason);
+        }
+
+        // preview move to find out if a promote piece must be chosen
+        let is_promotion = board
+            .pre_move_result(from_coord, to_coord, None)
+            .is_some_and(|move_result| {
+                move_result.is_promote_piece && move_result.promote_piece_type.is_none()
+            });
+
+        if is_promotion {
+            board.clear_highlights();
+            self.pending_promotion = Some((from_coord, to_coord));
+            return SelectionResult::new(SelectionAction::NeedsPromotion, Some(from_coord));
+        }
+
+        let move_result = board.move_piece(from_coord, to_coord, None);
+        self.clear(board);
+
+        match move_result {
+            Some(move_result) => SelectionResult {
+                move_result: Some(move_result),
+                ..SelectionResult::new(SelectionAction::Move, None)
+            },
+            None => SelectionResult::illegal(InvalidMoveReason::VariantRule),
+        }
+    }
+
+    /// clear selected tile and move highlights
+    fn clear(&mut self, board: &mut Board) {
+        board.clear_active_tiles();
+        board.clear_highlights();
+        self.selected = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::pieces::piece::PieceType;
+    use crate::pieces::strategy::InvalidMoveReason;
+    use crate::selection::{SelectionAction, SelectionController};
+    use crate::tile::{TileCoord, TileState};
+
+    #[test]
+    pub fn test_selection_controller() {
+        let mut board = Board::from_fen("4k3/P7/8/8/8/8/4P3/4K1N1 w - - 0 1").unwrap();
+        let mut controller = SelectionController::new();
+        let (knight, pawn, king) = (
+            TileCoord::new(0, 6),
+            TileCoord::new(1, 4),
+            TileCoord::new(0, 4),
+        );
+
+        // enemy piece cannot be selected
+        let result = controller.click(TileCoord::new(7, 4), &mut board);
+        assert_eq!(Some(InvalidMoveReason::NotYourTurn), result.reason);
+
+        let result = controller.click(knight, &mut board);
+        assert_eq!(SelectionAction::Select, result.action);
+        assert_eq!(
+            TileState::Highlight,
+            board.tile_at_index(21).unwrap().state()
+        );
+
+        let result = controller.click(pawn, &mut board);
+        assert_eq!(SelectionAction::Reselect, result.action);
+        assert_eq!(
+            TileState::Inactive,
+            board.tile_at_index(21).unwrap().state()
+        );
+
+        let result = controller.click(pawn, &mut board);
+        assert_eq!(SelectionAction::Deselect, result.action);
+        assert_eq!(None, board.get_selected_piece_coord());
+
+        // illegal move clears the selection
+        controller.drag_start(king, &mut board);
+        let result = controller.drop(TileCoord::new(2, 4), &mut board);
+        assert_eq!(SelectionAction::Illegal, result.action);
+        assert_eq!(None, controller.selected());
+
+        controller.drag_start(pawn, &mut board);
+        let result = controller.drop(TileCoord::new(3, 4), &mut board);
+        assert_eq!(SelectionAction::Move, result.action);
+        assert!(result.move_result().is_some());
+
+        // black king move, then white promotes
+        board.move_piece(TileCoord::new(7, 4), TileCoord::new(7, 3), None);
+        controller.click(TileCoord::new(6, 0), &mut board);
+        let result = controller.click(TileCoord::new(7, 0), &mut board);
+        assert_eq!(SelectionAction::NeedsPromotion, result.action);
+
+        let result = controller.promote(PieceType::Knight, &mut board);
+        assert_eq!(SelectionAction::Move, result.action);
+        assert_eq!(
+            Some(PieceType::Knight),
+            board
+                .get_piece(&TileCoord::new(7, 0))
+                .map(|piece| piece.piece_type())
+        );
+    }
+}
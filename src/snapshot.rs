@@ -0,0 +1,269 @@
+use crate::board::Board;
+use crate::error::{ChessError, ChessResult};
+use crate::pieces::king::KingCastleState;
+use crate::pieces::piece::{Piece, PieceColor, PieceType};
+use crate::tile::{TileCoord, TileState};
+
+/// Snapshot format version, first byte of the header
+pub const SNAPSHOT_VERSION: u8 = 2;
+/// Header bytes before the 64 tile bytes
+pub const SNAPSHOT_HEADER_LEN: usize = 4;
+/// Total snapshot length, header, tile bytes and piece ids
+pub const SNAPSHOT_LEN: usize = SNAPSHOT_HEADER_LEN + 64 * 5;
+/// En passant header byte when there is no en passant pawn
+pub const SNAPSHOT_NO_EN_PASSANT: u8 = 255;
+
+// castling header bits, same order as in FEN
+const WHITE_SHORT_CASTLE: u8 = 1;
+const WHITE_LONG_CASTLE: u8 = 2;
+const BLACK_SHORT_CASTLE: u8 = 4;
+const BLACK_LONG_CASTLE: u8 = 8;
+
+/// Compact board encoding used to render the board without
+/// cloning every tile across the wasm boundary
+///
+/// header:
+/// 0: version
+/// 1: active color, 0 white, 1 black
+/// 2: castling bits, 1 white short, 2 white long, 4 black short, 8 black long
+/// 3: tile index of the en passant pawn, 255 if none
+///
+/// followed by one byte per tile in tile index order,
+/// low 4 bits are the piece code and high 4 bits the `TileState`,
+/// piece code is 0 for an empty tile, 1 pawn, 2 knight, 3 bishop, 4 rook,
+/// 5 queen, 6 king, plus 8 for black pieces,
+/// states other than the known `TileState`s decode as `TileState::Unknown`
+///
+/// followed by the piece id of every tile in tile index order,
+/// 4 bytes little endian, 0 for an empty tile
+///
+/// variant, move clocks, pockets and chess960 rook files are not included,
+/// use `Board::to_bytes` or FEN for a complete copy of the board
+pub struct Snapshot {}
+
+impl Snapshot {
+    pub fn encode(board: &Board) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SNAPSHOT_LEN);
+
+        bytes.push(SNAPSHOT_VERSION);
+        bytes.push(match board.active_color() {
+            PieceColor::White => 0,
+            PieceColor::Black => 1,
+        });
+        bytes.push(Snapshot::castling_bits(board));
+        bytes.push(match board.last_en_passant() {
+            Some(coord) => Board::tile_idx_from_coord(&coord) as u8,
+            None => SNAPSHOT_NO_EN_PASSANT,
+        });
+
+        for i in 0..board.num_tiles() {
+            let coord: TileCoord = i.into();
+            let piece_code = match board.get_piece(&coord) {
+                Some(piece) => Snapshot::piece_code(piece.piece_type(), piece.color()),
+                None => 0,
+            };
+            // SAFETY: index is always on the board
+            let state = board.tile_at_index(i).unwrap().state() as u8;
+
+            bytes.push((state << 4) | piece_code);
+        }
+
+        for i in 0..board.num_tiles() {
+            let piece_id = board.get_piece(&i.into()).map_or(0, |piece| piece.id());
+            bytes.extend_from_slice(&piece_id.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> ChessResult<Board> {
+        if bytes.len() != SNAPSHOT_LEN {
+            return Err(ChessError::Deserialize(format!(
+                "snapshot must be {SNAPSHOT_LEN} bytes"
+            )));
+        }
+
+        if bytes[0] != SNAPSHOT_VERSION {
+            return Err(ChessError::Deserialize(format!(
+                "unknown snapshot version {}",
+                bytes[0]
+            )));
+        }
+
+        let mut board = Board::empty();
+
+        board.set_active_color(match bytes[1] {
+            0 => PieceColor::White,
+            1 => PieceColor::Black,
+            color => {
+                return Err(ChessError::Deserialize(format!(
+                    "invalid snapshot color {color}"
+                )))
+            }
+        });
+
+        let (tile_bytes, id_bytes) = bytes[SNAPSHOT_HEADER_LEN..].split_at(64);
+        for (i, (byte, id)) in tile_bytes.iter().zip(id_bytes.chunks_exact(4)).enumerate() {
+            let coord: TileCoord = i.into();
+            if let Some((piece_type, piece_color)) = Snapshot::from_piece_code(byte & 0x0f)? {
+                let mut piece = Piece::new(piece_type, piece_color, coord);
+                piece.set_id(u32::from_le_bytes([id[0], id[1], id[2], id[3]]));
+                board.place_piece(&coord, piece);
+            }
+            board.update_tile_state(&coord, TileState::from(byte >> 4))?;
+        }
+
+        let mut castle_state = board.king_castle_state();
+        castle_state.white_king =
+            Snapshot::castle_state(bytes[2], WHITE_SHORT_CASTLE, WHITE_LONG_CASTLE);
+        castle_state.black_king =
+            Snapshot::castle_state(bytes[2], BLACK_SHORT_CASTLE, BLACK_LONG_CASTLE);
+        board.set_king_castle_state(castle_state);
+
+        if bytes[3] != SNAPSHOT_NO_EN_PASSANT {
+            let index = bytes[3] as usize;
+            if index >= board.num_tiles() {
+                return Err(ChessError::TileIndexOutOfBounds(index));
+            }
+            board.set_last_en_passant(Some(index.into()));
+        }
+
+        Ok(board)
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    fn piece_code(piece_type: PieceType, piece_color: PieceColor) -> u8 {
+        let code = match piece_type {
+            PieceType::Pawn => 1,
+            PieceType::Knight => 2,
+            PieceType::Bishop => 3,
+            PieceType::Rook => 4,
+            PieceType::Queen => 5,
+            PieceType::King => 6,
+        };
+
+        match piece_color {
+            PieceColor::White => code,
+            PieceColor::Black => code + 8,
+        }
+    }
+
+    fn from_piece_code(code: u8) -> ChessResult<Option<(PieceType, PieceColor)>> {
+        if code == 0 {
+            return Ok(None);
+        }
+
+        let piece_color = if code & 8 == 0 {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+
+        let piece_type = match code & 7 {
+            1 => PieceType::Pawn,
+            2 => PieceType::Knight,
+            3 => PieceType::Bishop,
+            4 => PieceType::Rook,
+            5 => PieceType::Queen,
+            6 => PieceType::King,
+            _ => {
+                return Err(ChessError::Deserialize(format!(
+                    "invalid snapshot piece code {code}"
+                )))
+            }
+        };
+
+        Ok(Some((piece_type, piece_color)))
+    }
+
+    fn castling_bits(board: &Board) -> u8 {
+        let castle_state = board.king_castle_state();
+        let mut bits = 0;
+
+        for (king, short_bit, long_bit) in [
+            (
+                castle_state.white_king,
+                WHITE_SHORT_CASTLE,
+                WHITE_LONG_CASTLE,
+            ),
+            (
+                castle_state.black_king,
+                BLACK_SHORT_CASTLE,
+                BLACK_LONG_CASTLE,
+            ),
+        ] {
+            if king.is_king_moved {
+                continue;
+            }
+            if !king.h_file_rook_moved {
+                bits |= short_bit;
+            }
+            if !king.a_file_rook_moved {
+                bits |= long_bit;
+            }
+        }
+
+        bits
+    }
+
+    fn castle_state(bits: u8, short_bit: u8, long_bit: u8) -> KingCastleState {
+        KingCastleState::from_rights(bits & short_bit != 0, bits & long_bit != 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::fen::Fen;
+    use crate::pieces::piece::{PieceColor, PieceType};
+    use crate::snapshot::{Snapshot, SNAPSHOT_HEADER_LEN, SNAPSHOT_LEN};
+    use crate::tile::{TileCoord, TileState};
+
+    #[test]
+    pub fn test_snapshot_round_trip() {
+        let fen = "r3k2r/pp3ppp/8/3pP3/8/8/PPP2PPP/R3K2R w Kq d6 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        board
+            .set_tile_state(&TileCoord::new(0, 4), TileState::Active)
+            .unwrap();
+
+        let bytes = Snapshot::encode(&board);
+        assert_eq!(SNAPSHOT_LEN, bytes.len());
+        // white king on e1 with active tile state
+        assert_eq!((1 << 4) | 6, bytes[SNAPSHOT_HEADER_LEN + 4]);
+
+        let mut decoded = Snapshot::decode(&bytes).unwrap();
+        assert_eq!(Fen::write_position(&board), Fen::write_position(&decoded));
+        assert_eq!(
+            Some(TileCoord::new(0, 4)),
+            decoded.get_selected_piece_coord()
+        );
+
+        // piece ids are kept and new pieces get unused ids
+        let piece_ids = |board: &Board| -> Vec<Option<u32>> {
+            (0..board.num_tiles())
+                .map(|i| board.get_piece(&i.into()).map(|piece| piece.id()))
+                .collect()
+        };
+        assert_eq!(piece_ids(&board), piece_ids(&decoded));
+
+        let empty = TileCoord::new(3, 0);
+        decoded.set_new_tile(&empty, Some(PieceType::Queen), Some(PieceColor::White));
+        let max_id = piece_ids(&board).into_iter().flatten().max();
+        assert!(decoded.get_piece(&empty).map(|piece| piece.id()) > max_id);
+
+        // unknown tile states decode as unknown
+        let mut unknown = bytes.clone();
+        unknown[SNAPSHOT_HEADER_LEN] = (15 << 4) | 1;
+        let decoded = Snapshot::decode(&unknown).unwrap();
+        assert_eq!(
+            TileState::Unknown,
+            decoded.tile_by_index(0).unwrap().state()
+        );
+
+        assert!(Snapshot::decode(&bytes[1..]).is_err());
+    }
+}
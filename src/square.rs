@@ -0,0 +1,261 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::ChessError;
+use crate::pieces::piece::PieceColor;
+use crate::tile::TileCoord;
+
+/// Row and col offsets of the eight knight jumps
+pub const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (-2, -1),
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+    (2, 1),
+    (1, 2),
+    (-1, 2),
+    (-2, 1),
+];
+
+/// Direction of a single step on the board,
+/// north is towards rank 8 and east towards the h-file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Direction {
+    /// rook directions
+    pub const STRAIGHT: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    /// bishop directions
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::NorthWest,
+        Direction::SouthEast,
+        Direction::SouthWest,
+    ];
+
+    /// queen and king directions
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+        Direction::NorthEast,
+        Direction::NorthWest,
+        Direction::SouthEast,
+        Direction::SouthWest,
+    ];
+
+    /// row and col offset of one step
+    pub fn offset(&self) -> (i8, i8) {
+        match self {
+            Self::North => (1, 0),
+            Self::South => (-1, 0),
+            Self::East => (0, 1),
+            Self::West => (0, -1),
+            Self::NorthEast => (1, 1),
+            Self::NorthWest => (1, -1),
+            Self::SouthEast => (-1, 1),
+            Self::SouthWest => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let (row_offset, col_offset) = self.offset();
+        row_offset != 0 && col_offset != 0
+    }
+
+    /// direction pawns of piece color move in
+    pub fn forward(piece_color: PieceColor) -> Direction {
+        match piece_color {
+            PieceColor::White => Direction::North,
+            PieceColor::Black => Direction::South,
+        }
+    }
+
+    fn from_offset(offset: (i8, i8)) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+}
+
+/// Tile on the board which is always in bounds,
+/// stepping off the board returns None instead of wrapping around
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Square {
+    row: u8,
+    col: u8,
+}
+
+impl Square {
+    /// None if row or col is not on the board
+    pub fn new(row: u8, col: u8) -> Option<Square> {
+        if row > 7 || col > 7 {
+            return None;
+        }
+        Some(Self { row, col })
+    }
+
+    /// all 64 squares in tile index order
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..8).flat_map(|row| (0..8).map(move |col| Self { row, col }))
+    }
+
+    pub fn row(&self) -> u8 {
+        self.row
+    }
+
+    pub fn col(&self) -> u8 {
+        self.col
+    }
+
+    pub fn coord(&self) -> TileCoord {
+        TileCoord::new(self.row, self.col)
+    }
+
+    /// square offset by rows and cols, None if off the board
+    pub fn offset(&self, row_offset: i8, col_offset: i8) -> Option<Square> {
+        let row = self.row.checked_add_signed(row_offset)?;
+        let col = self.col.checked_add_signed(col_offset)?;
+        Square::new(row, col)
+    }
+
+    /// next square in direction, None at the edge of the board
+    pub fn step(&self, direction: Direction) -> Option<Square> {
+        let (row_offset, col_offset) = direction.offset();
+        self.offset(row_offset, col_offset)
+    }
+
+    /// squares in direction up to the edge of the board, not including self
+    pub fn ray(&self, direction: Direction) -> Ray {
+        Ray {
+            next: self.step(direction),
+            direction,
+        }
+    }
+
+    /// direction from self towards other square,
+    /// None if not on the same row, col or diagonal
+    pub fn direction_to(&self, other: Square) -> Option<Direction> {
+        let row_diff = other.row as i8 - self.row as i8;
+        let col_diff = other.col as i8 - self.col as i8;
+
+        if row_diff != 0 && col_diff != 0 && row_diff.abs() != col_diff.abs() {
+            return None;
+        }
+
+        Direction::from_offset((row_diff.signum(), col_diff.signum()))
+    }
+
+    /// squares strictly between self and other square,
+    /// empty if not on the same row, col or diagonal
+    pub fn between(&self, other: Square) -> Vec<Square> {
+        match self.direction_to(other) {
+            Some(direction) => self
+                .ray(direction)
+                .take_while(|square| *square != other)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl TryFrom<TileCoord> for Square {
+    type Error = ChessError;
+
+    fn try_from(coord: TileCoord) -> Result<Self, Self::Error> {
+        Square::new(coord.row(), coord.col()).ok_or(ChessError::CoordOutOfBounds(coord))
+    }
+}
+
+impl From<Square> for TileCoord {
+    fn from(square: Square) -> Self {
+        square.coord()
+    }
+}
+
+/// parse square in algebraic notation, ie. "e4"
+impl FromStr for Square {
+    type Err = ChessError;
+
+    fn from_str(algebraic: &str) -> Result<Self, Self::Err> {
+        let invalid = || ChessError::InvalidSquare(algebraic.to_string());
+
+        let [file, rank] = algebraic.as_bytes() else {
+            return Err(invalid());
+        };
+        if !(b'a'..=b'h').contains(file) || !(b'1'..=b'8').contains(rank) {
+            return Err(invalid());
+        }
+
+        Square::new(rank - b'1', file - b'a').ok_or_else(invalid)
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.coord().algebraic())
+    }
+}
+
+/// Iterator over the squares in one direction, see `Square::ray`
+pub struct Ray {
+    next: Option<Square>,
+    direction: Direction,
+}
+
+impl Iterator for Ray {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let square = self.next?;
+        self.next = square.step(self.direction);
+        Some(square)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::square::{Direction, Square};
+
+    #[test]
+    pub fn test_square() {
+        let a1: Square = "a1".parse().unwrap();
+        let h8: Square = "h8".parse().unwrap();
+
+        // stepping off the edge does not wrap
+        assert_eq!(None, a1.step(Direction::South));
+        assert_eq!(None, a1.offset(-1, -2));
+        assert_eq!(None, h8.offset(1, 0));
+        assert_eq!(Some("b3".parse().unwrap()), a1.offset(2, 1));
+
+        assert_eq!(7, a1.ray(Direction::NorthEast).count());
+        assert_eq!(Some(h8), a1.ray(Direction::NorthEast).last());
+        assert_eq!(0, h8.ray(Direction::East).count());
+
+        assert_eq!(Some(Direction::NorthEast), a1.direction_to(h8));
+        assert_eq!(6, a1.between(h8).len());
+        assert!(a1.between("b3".parse().unwrap()).is_empty());
+
+        for invalid in ["", "e", "e9", "i1", "e44", "E4"] {
+            assert!(invalid.parse::<Square>().is_err());
+        }
+        assert_eq!("e4", "e4".parse::<Square>().unwrap().to_string());
+        assert_eq!(64, Square::all().count());
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::Display;
 use wasm_bindgen::prelude::*;
 
 use crate::console_log;
+use crate::error::{ChessError, ChessResult};
 use crate::pieces::piece::{Piece, PieceColor, PieceState, PieceType};
 
 #[wasm_bindgen]
@@ -14,6 +15,24 @@ pub struct Tile {
     rank: TileRank,
     index: u8,
     piece: Option<Piece>,
+    #[serde(default)]
+    markers: TileMarkers,
+}
+
+impl Tile {
+    pub fn markers_mut(&mut self) -> &mut TileMarkers {
+        &mut self.markers
+    }
+
+    /// set state of piece on tile, see `set_piece_state`
+    pub fn update_piece_state(&mut self, piece_state: PieceState) -> ChessResult<()> {
+        let piece = self
+            .piece
+            .as_mut()
+            .ok_or(ChessError::EmptyTile(self.coord))?;
+        piece.set_state(piece_state);
+        Ok(())
+    }
 }
 
 #[wasm_bindgen]
@@ -36,6 +55,7 @@ impl Tile {
             file: col.into(),
             rank: row.into(),
             piece,
+            markers: TileMarkers::default(),
         }
     }
 
@@ -74,9 +94,8 @@ impl Tile {
         self.piece = Some(new_piece);
     }
 
-    pub fn set_piece_state(&mut self, piece_state: PieceState) {
-        let piece = self.piece.as_mut().unwrap();
-        piece.set_state(piece_state);
+    pub fn set_piece_state(&mut self, piece_state: PieceState) -> Result<(), JsValue> {
+        Ok(self.update_piece_state(piece_state)?)
     }
 
     pub fn coord(&self) -> TileCoord {
@@ -87,20 +106,38 @@ impl Tile {
         self.state
     }
 
-    pub fn as_bytes(&self) -> Vec<u8> {
-        bincode::serialize(&self).unwrap()
+    /// tile is highlighted as a move target, quiet or capture
+    pub fn is_highlighted(&self) -> bool {
+        matches!(self.state, TileState::Highlight | TileState::Capture)
+    }
+
+    pub fn markers(&self) -> TileMarkers {
+        self.markers
+    }
+
+    pub fn set_markers(&mut self, markers: TileMarkers) {
+        self.markers = markers
+    }
+
+    pub fn as_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        let bytes =
+            bincode::serialize(&self).map_err(|err| ChessError::Serialize(err.to_string()))?;
+        Ok(bytes)
     }
 
     pub fn log(&self) {
         console_log!("{:?}", self);
     }
 
-    pub fn to_json(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self).unwrap()
+    pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        let json = serde_wasm_bindgen::to_value(&self)
+            .map_err(|err| ChessError::Serialize(err.to_string()))?;
+        Ok(json)
     }
 
-    pub fn from_json(json: JsValue) -> Tile {
-        serde_wasm_bindgen::from_value(json).unwrap()
+    pub fn from_json(json: JsValue) -> Result<Tile, JsValue> {
+        let tile = serde_wasm_bindgen::from_value(json).map_err(ChessError::from)?;
+        Ok(tile)
     }
 
     // ---
@@ -108,7 +145,7 @@ impl Tile {
     // ---
 
     fn get_tile_color(&self) -> TileColor {
-        if (self.coord.col + self.coord.row) % 2 == 0 {
+        if (self.coord.col + self.coord.row).is_multiple_of(2) {
             TileColor::Black
         } else {
             TileColor::White
@@ -122,15 +159,36 @@ pub enum TileState {
     Inactive,
     Active,
     Highlight,
+    /// tile is the from or to coord of a queued premove
+    Premove,
+    /// highlighted move target which takes a piece
+    Capture,
     Unknown,
 }
 
+/// Markers shown on a tile independent of its `TileState`,
+/// each marker is set and cleared by the board on its own
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub struct TileMarkers {
+    /// tile is the from coord of the last move
+    pub last_move_from: bool,
+    /// tile is the to coord of the last move
+    pub last_move_to: bool,
+    /// tile has a king in check
+    pub check: bool,
+    /// tile is under the mouse pointer
+    pub hover: bool,
+}
+
 impl Display for TileState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Active => write!(f, "A"),
             Self::Inactive => write!(f, "I"),
             Self::Highlight => write!(f, "S"),
+            Self::Premove => write!(f, "P"),
+            Self::Capture => write!(f, "C"),
             _ => write!(f, "UNKNOWN"),
         }
     }
@@ -142,6 +200,8 @@ impl From<u8> for TileState {
             0 => Self::Inactive,
             1 => Self::Active,
             2 => Self::Highlight,
+            3 => Self::Premove,
+            4 => Self::Capture,
             _ => Self::Unknown,
         }
     }
@@ -347,12 +407,20 @@ impl TileCoord {
         (self.row <= 7) && (self.col <= 7)
     }
 
-    pub fn to_json(self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self).unwrap()
+    /// returns coord in algebraic notation, ie. "e4"
+    pub fn algebraic(&self) -> String {
+        format!("{}{}", self.file(), self.rank())
+    }
+
+    pub fn to_json(self) -> Result<JsValue, JsValue> {
+        let json = serde_wasm_bindgen::to_value(&self)
+            .map_err(|err| ChessError::Serialize(err.to_string()))?;
+        Ok(json)
     }
 
-    pub fn from_json(json: JsValue) -> Self {
-        serde_wasm_bindgen::from_value(json).unwrap()
+    pub fn from_json(json: JsValue) -> Result<TileCoord, JsValue> {
+        let coord = serde_wasm_bindgen::from_value(json).map_err(ChessError::from)?;
+        Ok(coord)
     }
 }
 
@@ -0,0 +1,145 @@
+use crate::board::Board;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::pieces::strategy::{MoveHandler, MoveValidator, PieceMoveStrategy, StrategyBuilder};
+use crate::variants::variant::{Variant, VariantRules};
+
+/// Giveaway chess, takes are compulsory and the king is an ordinary piece
+pub struct AntichessRules {}
+
+impl AntichessRules {
+    /// check if any piece of piece color can take
+    pub fn can_take(piece_color: PieceColor, board: &Board) -> bool {
+        for coord in MoveHandler::own_piece_coords(piece_color, board) {
+            // SAFETY:
+            // tile has piece, confirmed in own_piece_coords
+            let piece = board.peek_tile(&coord).unwrap();
+            let piece_strategy = StrategyBuilder::new_piece_strategy(
+                piece.piece_type(),
+                coord,
+                piece.color(),
+                board,
+            );
+
+            for new_coord in piece_strategy.moves() {
+                if !new_coord.in_bounds() {
+                    continue;
+                }
+
+                let move_validator = MoveValidator::new(new_coord, board);
+                if AntichessRules::is_take(piece_strategy.as_ref(), &move_validator)
+                    && move_validator.is_valid_move(piece_strategy.as_ref(), true)
+                {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn is_take(piece_strategy: &dyn PieceMoveStrategy, move_validator: &MoveValidator) -> bool {
+        move_validator.is_take() || move_validator.is_en_passant_take(piece_strategy)
+    }
+}
+
+impl VariantRules for AntichessRules {
+    fn variant(&self) -> Variant {
+        Variant::Antichess
+    }
+
+    fn has_check(&self) -> bool {
+        false
+    }
+
+    fn has_castling(&self) -> bool {
+        false
+    }
+
+    /// king is an ordinary piece and can be promoted to
+    fn promote_piece_types(&self) -> Vec<PieceType> {
+        vec![
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+            PieceType::King,
+        ]
+    }
+
+    /// non take moves are only valid if no piece can take
+    fn is_valid_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+        move_validator: &MoveValidator,
+        board: &Board,
+    ) -> bool {
+        AntichessRules::is_take(piece_strategy, move_validator)
+            || !AntichessRules::can_take(piece_strategy.color(), board)
+    }
+
+    /// player who loses all pieces or has no valid move wins
+    fn winner(&self, board: &Board) -> Option<PieceColor> {
+        if let Some(piece_color) = [PieceColor::White, PieceColor::Black]
+            .into_iter()
+            .find(|piece_color| MoveHandler::own_piece_coords(*piece_color, board).is_empty())
+        {
+            return Some(piece_color);
+        }
+
+        let active_color = board.active_color();
+        if !MoveValidator::has_valid_move(active_color, board) {
+            return Some(active_color);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::game::Game;
+    use crate::pieces::piece::PieceColor;
+    use crate::tile::TileCoord;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_antichess() {
+        // takes are compulsory
+        let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+        let mut board = Board::from_variant_fen(fen, Variant::Antichess).unwrap();
+        assert!(board
+            .move_piece(TileCoord::new(0, 4), TileCoord::new(1, 4), None)
+            .is_none());
+        assert!(board
+            .move_piece(TileCoord::new(3, 4), TileCoord::new(4, 3), None)
+            .is_some());
+
+        // king can be taken, losing all pieces wins
+        let fen = "8/8/8/8/8/8/3k4/4K3 w - - 0 1";
+        let mut game = Game::from_variant_fen(fen, Variant::Antichess).unwrap();
+        game.add_move("e1d2".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!("8/8/8/8/8/8/3K4/8 b - - 0 1", game.board().to_fen());
+        assert_eq!(Some(PieceColor::Black), game.get_winner());
+
+        // king can move onto attacked tile and pinned piece can move
+        let fen = "7k/4r3/8/8/8/8/r3B3/4K3 w - - 0 1";
+        let board = Board::from_variant_fen(fen, Variant::Antichess).unwrap();
+        assert!(board
+            .clone()
+            .move_piece(TileCoord::new(0, 4), TileCoord::new(1, 3), None)
+            .is_some());
+        assert!(board
+            .clone()
+            .move_piece(TileCoord::new(1, 4), TileCoord::new(2, 3), None)
+            .is_some());
+
+        // pawn can promote to king
+        let fen = "8/P7/8/8/8/8/8/k6K w - - 0 1";
+        let mut game = Game::from_variant_fen(fen, Variant::Antichess).unwrap();
+        game.add_move("a7a8=K".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!("K7/8/8/8/8/8/8/k6K b - - 0 1", game.board().to_fen());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::board::Board;
+use crate::parser::{MoveEffect, MoveResult};
+use crate::pieces::attack::AttackValidator;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::pieces::strategy::{MoveValidator, PieceMoveStrategy};
+use crate::tile::TileCoord;
+use crate::variants::variant::{Variant, VariantRules};
+
+pub struct AtomicRules {}
+
+impl AtomicRules {
+    /// coords next to the take coord, pieces on these coords
+    /// are exploded unless they are pawns
+    pub fn blast_coords(coord: TileCoord) -> Vec<TileCoord> {
+        let (row, col) = (coord.row() as i8, coord.col() as i8);
+        let mut coords = vec![];
+
+        for row_offset in -1..=1 {
+            for col_offset in -1..=1 {
+                if row_offset == 0 && col_offset == 0 {
+                    continue;
+                }
+
+                let (new_row, new_col) = (row + row_offset, col + col_offset);
+                if (0..8).contains(&new_row) && (0..8).contains(&new_col) {
+                    coords.push(TileCoord::new(new_row as u8, new_col as u8));
+                }
+            }
+        }
+
+        coords
+    }
+
+    fn is_king_in_blast(piece_color: PieceColor, take_coord: TileCoord, board: &Board) -> bool {
+        AtomicRules::blast_coords(take_coord)
+            .iter()
+            .filter_map(|coord| board.get_piece(coord))
+            .any(|piece| piece.piece_type() == PieceType::King && piece.color() == piece_color)
+    }
+
+    fn is_take(piece_strategy: &dyn PieceMoveStrategy, move_validator: &MoveValidator) -> bool {
+        move_validator.is_take() || move_validator.is_en_passant_take(piece_strategy)
+    }
+}
+
+impl VariantRules for AtomicRules {
+    fn variant(&self) -> Variant {
+        Variant::Atomic
+    }
+
+    /// explosions can remove pieces from the line of a pin or check
+    fn has_pins(&self) -> bool {
+        false
+    }
+
+    /// king cannot take and no take may explode own king
+    fn is_valid_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+        move_validator: &MoveValidator,
+        board: &Board,
+    ) -> bool {
+        if !AtomicRules::is_take(piece_strategy, move_validator) {
+            return true;
+        }
+
+        if piece_strategy.piece_type() == PieceType::King {
+            return false;
+        }
+
+        !AtomicRules::is_king_in_blast(piece_strategy.color(), move_validator.new_coord(), board)
+    }
+
+    /// exploding the enemy king wins even if own king is in check
+    fn is_winning_move(
+        &self,
+        piece_strategy: &dyn PieceMoveStrategy,
+        move_validator: &MoveValidator,
+        board: &Board,
+    ) -> bool {
+        AtomicRules::is_take(piece_strategy, move_validator)
+            && AtomicRules::is_king_in_blast(
+                PieceColor::opposite_color(piece_strategy.color()),
+                move_validator.new_coord(),
+                board,
+            )
+    }
+
+    /// taking piece, taken piece and all adjacent non pawn pieces are removed
+    fn handle_move(&self, move_result: &mut MoveResult, board: &mut Board) {
+        if !move_result.is_take {
+            return;
+        }
+
+        let mut blast_coords = AtomicRules::blast_coords(move_result.to_coord);
+        blast_coords.insert(0, move_result.to_coord);
+
+        for coord in blast_coords {
+            if let Some(piece) = board.get_piece(&coord) {
+                if coord == move_result.to_coord || piece.piece_type() != PieceType::Pawn {
+                    board.set_new_tile(&coord, None, None);
+                    move_result.effects.push(MoveEffect::removed(
+                        piece.piece_type(),
+                        piece.color(),
+                        coord,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// player whose king was exploded loses
+    fn winner(&self, board: &Board) -> Option<PieceColor> {
+        [PieceColor::White, PieceColor::Black]
+            .into_iter()
+            .find(|piece_color| AttackValidator::king_coord(*piece_color, board).is_none())
+            .map(PieceColor::opposite_color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::game::Game;
+    use crate::parser::MoveEffect;
+    use crate::pieces::piece::{PieceColor, PieceType};
+    use crate::tile::TileCoord;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_atomic() {
+        // take explodes adjacent pieces except pawns
+        let fen = "4k3/8/1b6/np6/8/8/8/R3K3 w - - 0 1";
+        let mut board = Board::from_variant_fen(fen, Variant::Atomic).unwrap();
+        let move_result = board
+            .move_piece(TileCoord::new(0, 0), TileCoord::new(4, 0), None)
+            .unwrap();
+        assert_eq!("4k3/8/8/1p6/8/8/8/4K3 b - - 0 1", board.to_fen());
+
+        // taken knight, moved rook, exploded rook and bishop
+        assert_eq!(4, move_result.effects.len());
+        assert_eq!(
+            MoveEffect::removed(PieceType::Bishop, PieceColor::Black, TileCoord::new(5, 1)),
+            move_result.effects[3]
+        );
+        assert_eq!(None, board.variant_winner());
+
+        // king cannot take
+        let fen = "4k3/8/8/8/8/8/3p4/4K3 w - - 0 1";
+        let mut board = Board::from_variant_fen(fen, Variant::Atomic).unwrap();
+        assert!(board
+            .move_piece(TileCoord::new(0, 4), TileCoord::new(1, 3), None)
+            .is_none());
+
+        // exploding enemy king wins
+        let fen = "3k4/4q3/8/8/8/8/8/4R1K1 w - - 0 1";
+        let mut game = Game::from_variant_fen(fen, Variant::Atomic).unwrap();
+        game.add_move("e1e7".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!(Some(PieceColor::White), game.get_winner());
+    }
+}
@@ -0,0 +1,207 @@
+use js_sys::Array;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::parser::MoveResult;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::variants::variant::{Variant, VariantRules};
+
+/// Pieces of one color which can be dropped on the board
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Copy)]
+pub struct Pocket {
+    pub pawns: u8,
+    pub knights: u8,
+    pub bishops: u8,
+    pub rooks: u8,
+    pub queens: u8,
+}
+
+#[wasm_bindgen]
+impl Pocket {
+    pub fn count(&self, piece_type: PieceType) -> u8 {
+        match piece_type {
+            PieceType::Pawn => self.pawns,
+            PieceType::Knight => self.knights,
+            PieceType::Bishop => self.bishops,
+            PieceType::Rook => self.rooks,
+            PieceType::Queen => self.queens,
+            PieceType::King => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Pocket::piece_types()
+            .iter()
+            .all(|piece_type| self.count(*piece_type) == 0)
+    }
+
+    /// JS array of piece types in the pocket, one entry per piece
+    /// ordered from queen to pawn, used to render the pocket
+    pub fn js_pieces(&self) -> Array {
+        self.pieces().into_iter().map(JsValue::from).collect()
+    }
+}
+
+impl Pocket {
+    /// piece types which can be held in a pocket, ordered from queen to pawn
+    pub fn piece_types() -> [PieceType; 5] {
+        [
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+            PieceType::Pawn,
+        ]
+    }
+
+    pub fn pieces(&self) -> Vec<PieceType> {
+        Pocket::piece_types()
+            .into_iter()
+            .flat_map(|piece_type| vec![piece_type; self.count(piece_type) as usize])
+            .collect()
+    }
+
+    fn count_mut(&mut self, piece_type: PieceType) -> Option<&mut u8> {
+        match piece_type {
+            PieceType::Pawn => Some(&mut self.pawns),
+            PieceType::Knight => Some(&mut self.knights),
+            PieceType::Bishop => Some(&mut self.bishops),
+            PieceType::Rook => Some(&mut self.rooks),
+            PieceType::Queen => Some(&mut self.queens),
+            PieceType::King => None,
+        }
+    }
+
+    /// kings can never be added to a pocket
+    pub fn add(&mut self, piece_type: PieceType) {
+        if let Some(count) = self.count_mut(piece_type) {
+            *count += 1;
+        }
+    }
+
+    /// returns false if piece type is not in the pocket
+    pub fn remove(&mut self, piece_type: PieceType) -> bool {
+        match self.count_mut(piece_type) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Copy)]
+pub struct Pockets {
+    pub white: Pocket,
+    pub black: Pocket,
+}
+
+impl Pockets {
+    pub fn pocket(&self, piece_color: PieceColor) -> &Pocket {
+        match piece_color {
+            PieceColor::White => &self.white,
+            PieceColor::Black => &self.black,
+        }
+    }
+
+    pub fn pocket_mut(&mut self, piece_color: PieceColor) -> &mut Pocket {
+        match piece_color {
+            PieceColor::White => &mut self.white,
+            PieceColor::Black => &mut self.black,
+        }
+    }
+}
+
+/// Taken pieces are added to the pocket of the taking player
+pub struct CrazyhouseRules {}
+
+impl VariantRules for CrazyhouseRules {
+    fn variant(&self) -> Variant {
+        Variant::Crazyhouse
+    }
+
+    fn has_pockets(&self) -> bool {
+        true
+    }
+
+    fn handle_move(&self, move_result: &mut MoveResult, board: &mut Board) {
+        if let Some(piece_type) = move_result.pocket_piece_type {
+            board.add_pocket_piece(piece_type, move_result.piece_color);
+        }
+    }
+}
+
+/// Taken pieces are passed to the partner on the other board,
+/// the frontend adds `MoveResult.pocket_piece_type` to the partners pocket
+pub struct BughouseRules {}
+
+impl VariantRules for BughouseRules {
+    fn variant(&self) -> Variant {
+        Variant::Bughouse
+    }
+
+    fn has_pockets(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::game::Game;
+    use crate::pieces::piece::{PieceColor, PieceType};
+    use crate::tile::TileCoord;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_crazyhouse_drops() {
+        let fen = "4k3/n7/8/8/8/8/8/R3K3[] w - - 0 1";
+        let mut game = Game::from_variant_fen(fen, Variant::Crazyhouse).unwrap();
+
+        // taken knight goes to white pocket
+        game.add_move("a1a7".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!(1, game.board().pocket(PieceColor::White).knights);
+        assert_eq!("4k3/R7/8/8/8/8/8/4K3[N] b - - 0 1", game.board().to_fen());
+
+        game.add_move("e8d8".to_string(), PieceColor::Black)
+            .unwrap();
+
+        // pawns cannot be dropped on the first or last rank
+        let mut board = game.board();
+        assert!(board
+            .drop_piece(PieceType::Pawn, PieceColor::White, &TileCoord::new(7, 0))
+            .is_none());
+
+        game.add_move("N@c6".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!("N@c6+", game.get_move(3).unwrap().san());
+        assert!(game.board().pocket(PieceColor::White).is_empty());
+
+        // drop must block check
+        let mut board = game.board();
+        assert!(board.pocket(PieceColor::Black).is_empty());
+        board.add_pocket_piece(PieceType::Rook, PieceColor::Black);
+        assert!(board
+            .drop_piece(PieceType::Rook, PieceColor::Black, &TileCoord::new(4, 4))
+            .is_none());
+    }
+
+    #[test]
+    pub fn test_crazyhouse_promoted_take() {
+        let fen = "r3k3/8/8/8/8/8/8/Q~3K3[] b - - 0 1";
+        let mut board = Board::from_variant_fen(fen, Variant::Crazyhouse).unwrap();
+        assert_eq!(fen, board.to_fen());
+
+        // promoted queen reverts to pawn
+        let move_result = board
+            .move_piece(TileCoord::new(7, 0), TileCoord::new(0, 0), None)
+            .unwrap();
+        assert_eq!(Some(PieceType::Pawn), move_result.pocket_piece_type);
+        assert_eq!("4k3/8/8/8/8/8/8/r3K3[p] w - - 0 2", board.to_fen());
+    }
+}
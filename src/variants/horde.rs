@@ -0,0 +1,59 @@
+use crate::board::Board;
+use crate::pieces::piece::PieceColor;
+use crate::pieces::strategy::MoveHandler;
+use crate::variants::variant::{Variant, VariantRules};
+
+/// white horde of 36 pawns against the black army
+pub const HORDE_FEN: &str =
+    "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+
+/// White wins by checkmate, black wins by taking every white piece
+pub struct HordeRules {}
+
+impl VariantRules for HordeRules {
+    fn variant(&self) -> Variant {
+        Variant::Horde
+    }
+
+    fn start_fen(&self) -> Option<&'static str> {
+        Some(HORDE_FEN)
+    }
+
+    fn winner(&self, board: &Board) -> Option<PieceColor> {
+        if MoveHandler::own_piece_coords(PieceColor::White, board).is_empty() {
+            return Some(PieceColor::Black);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::pieces::piece::PieceColor;
+    use crate::tile::TileCoord;
+    use crate::variants::horde::HORDE_FEN;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_horde() {
+        let board = Board::variant_start(Variant::Horde).unwrap();
+        assert_eq!(HORDE_FEN, board.to_fen());
+
+        // first rank pawns double move and can be taken en passant
+        let fen = "4k3/8/8/8/8/1p6/8/P7 w - - 0 1";
+        let mut board = Board::from_variant_fen(fen, Variant::Horde).unwrap();
+        board
+            .move_piece(TileCoord::new(0, 0), TileCoord::new(2, 0), None)
+            .unwrap();
+        assert_eq!("4k3/8/8/8/8/Pp6/8/8 b - a2 0 1", board.to_fen());
+
+        let move_result = board
+            .move_piece(TileCoord::new(2, 1), TileCoord::new(1, 0), None)
+            .unwrap();
+        assert!(move_result.is_take);
+        assert_eq!("4k3/8/8/8/8/8/p7/8 w - - 0 2", board.to_fen());
+        assert_eq!(Some(PieceColor::Black), board.variant_winner());
+    }
+}
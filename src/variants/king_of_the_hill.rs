@@ -0,0 +1,54 @@
+use crate::board::Board;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::tile::TileCoord;
+use crate::variants::variant::{Variant, VariantRules};
+
+pub struct KingOfTheHillRules {}
+
+impl KingOfTheHillRules {
+    /// d4, e4, d5 and e5
+    pub fn hill_coords() -> [TileCoord; 4] {
+        [
+            TileCoord::new(3, 3),
+            TileCoord::new(3, 4),
+            TileCoord::new(4, 3),
+            TileCoord::new(4, 4),
+        ]
+    }
+}
+
+impl VariantRules for KingOfTheHillRules {
+    fn variant(&self) -> Variant {
+        Variant::KingOfTheHill
+    }
+
+    /// first king to reach the hill wins
+    fn winner(&self, board: &Board) -> Option<PieceColor> {
+        KingOfTheHillRules::hill_coords()
+            .iter()
+            .filter_map(|coord| board.get_piece(coord))
+            .find(|piece| piece.piece_type() == PieceType::King)
+            .map(|piece| piece.color())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::Game;
+    use crate::pieces::piece::PieceColor;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_king_of_the_hill() {
+        let fen = "4k3/8/8/8/8/3K4/8/8 w - - 0 1";
+        let mut game = Game::from_variant_fen(fen, Variant::KingOfTheHill).unwrap();
+        assert_eq!(None, game.get_winner());
+
+        game.add_move("d3d4".to_string(), PieceColor::White)
+            .unwrap();
+        assert_eq!(Some(PieceColor::White), game.get_winner());
+
+        game.undo_move().unwrap();
+        assert_eq!(None, game.get_winner());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod antichess;
+pub mod atomic;
+pub mod crazyhouse;
+pub mod horde;
+pub mod king_of_the_hill;
+pub mod standard;
+pub mod three_check;
+pub mod variant;
@@ -0,0 +1,9 @@
+use crate::variants::variant::{Variant, VariantRules};
+
+pub struct StandardRules {}
+
+impl VariantRules for StandardRules {
+    fn variant(&self) -> Variant {
+        Variant::Standard
+    }
+}
@@ -0,0 +1,85 @@
+use crate::board::Board;
+use crate::error::{ChessError, ChessResult};
+use crate::parser::MoveResult;
+use crate::pieces::piece::PieceColor;
+use crate::pieces::strategy::MoveValidator;
+use crate::variants::variant::{CheckCount, Variant, VariantRules};
+
+pub const WINNING_CHECKS: u8 = 3;
+
+pub struct ThreeCheckRules {}
+
+impl VariantRules for ThreeCheckRules {
+    fn variant(&self) -> Variant {
+        Variant::ThreeCheck
+    }
+
+    fn update_state(&self, move_result: &MoveResult, board: &mut Board) {
+        let enemy_color = PieceColor::opposite_color(move_result.piece_color);
+        if MoveValidator::is_check(enemy_color, board) {
+            let mut check_count = board.check_count();
+            check_count.add_check(move_result.piece_color);
+            board.set_check_count(check_count);
+        }
+    }
+
+    fn winner(&self, board: &Board) -> Option<PieceColor> {
+        let check_count = board.check_count();
+        [PieceColor::White, PieceColor::Black]
+            .into_iter()
+            .find(|piece_color| check_count.checks(*piece_color) >= WINNING_CHECKS)
+    }
+
+    /// remaining checks for white and black, ie. "3+3" at the start of the game
+    fn write_fen_fields(&self, board: &Board) -> Vec<String> {
+        let check_count = board.check_count();
+        vec![format!(
+            "{}+{}",
+            WINNING_CHECKS.saturating_sub(check_count.white),
+            WINNING_CHECKS.saturating_sub(check_count.black)
+        )]
+    }
+
+    fn parse_fen_field(&self, field: &str, board: &mut Board) -> ChessResult<bool> {
+        let Some((white, black)) = field.split_once('+') else {
+            return Ok(false);
+        };
+
+        let remaining = |checks: &str| match checks.parse::<u8>() {
+            Ok(checks) if checks <= WINNING_CHECKS => Ok(WINNING_CHECKS - checks),
+            _ => Err(ChessError::InvalidFen(format!(
+                "invalid check count '{field}'"
+            ))),
+        };
+
+        board.set_check_count(CheckCount {
+            white: remaining(white)?,
+            black: remaining(black)?,
+        });
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::board::Board;
+    use crate::pieces::piece::PieceColor;
+    use crate::tile::TileCoord;
+    use crate::variants::variant::Variant;
+
+    #[test]
+    pub fn test_three_check() {
+        let fen = "4k3/8/8/8/8/8/8/R3K3 w - - 1+3 0 1";
+        let mut board = Board::from_variant_fen(fen, Variant::ThreeCheck).unwrap();
+        assert_eq!(2, board.check_count().white);
+        assert_eq!(fen, board.to_fen());
+
+        board
+            .move_piece(TileCoord::new(0, 0), TileCoord::new(7, 0), None)
+            .unwrap();
+        assert_eq!(3, board.check_count().white);
+        assert_eq!(Some(PieceColor::White), board.variant_winner());
+        assert_eq!("R3k3/8/8/8/8/8/8/4K3 b - - 0+3 1 1", board.to_fen());
+    }
+}
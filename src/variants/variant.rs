@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::error::ChessResult;
+use crate::parser::MoveResult;
+use crate::pieces::piece::{PieceColor, PieceType};
+use crate::pieces::strategy::{MoveValidator, PieceMoveStrategy};
+
+use crate::variants::antichess::AntichessRules;
+use crate::variants::atomic::AtomicRules;
+use crate::variants::crazyhouse::{BughouseRules, CrazyhouseRules};
+use crate::variants::horde::HordeRules;
+use crate::variants::king_of_the_hill::KingOfTheHillRules;
+use crate::variants::standard::StandardRules;
+use crate::variants::three_check::ThreeCheckRules;
+
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Copy)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// king reaching one of the four center tiles wins
+    KingOfTheHill,
+    /// giving three checks wins
+    ThreeCheck,
+    /// takes explode all non pawn pieces on adjacent tiles
+    Atomic,
+    /// takes are compulsory, losing all pieces wins
+    Antichess,
+    /// 36 white pawns against the black army
+    Horde,
+    /// taken pieces can be dropped back on the board
+    Crazyhouse,
+    /// crazyhouse played on two boards, taken pieces go to the partner
+    Bughouse,
+}
+
+/// Number of checks given by each color
+/// used by three check variant
+#[wasm_bindgen]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Copy)]
+pub struct CheckCount {
+    pub white: u8,
+    pub black: u8,
+}
+
+impl CheckCount {
+    pub fn checks(&self, piece_color: PieceColor) -> u8 {
+        match piece_color {
+            PieceColor::White => self.white,
+            PieceColor::Black => self.black,
+        }
+    }
+
+    pub fn add_check(&mut self, piece_color: PieceColor) {
+        match piece_color {
+            PieceColor::White => self.white += 1,
+            PieceColor::Black => self.black += 1,
+        }
+    }
+}
+
+/// Rules which differ between variants,
+/// standard chess rules are used for everything else
+pub trait VariantRules {
+    fn variant(&self) -> Variant;
+
+    /// taken pieces are held in pockets and can be dropped
+    fn has_pockets(&self) -> bool {
+        false
+    }
+
+    /// king can be checked and cannot be taken,
+    /// without check the king is an ordinary piece
+    fn has_check(&self) -> bool {
+        true
+    }
+
+    fn has_castling(&self) -> bool {
+        true
+    }
+
+    /// check and pins follow from the attacks of the enemy pieces,
+    /// false if a move removes more than the taken piece, eg. atomic explosions
+    fn has_pins(&self) -> bool {
+        true
+    }
+
+    /// piece types a pawn can be promoted to
+    fn promote_piece_types(&self) -> Vec<PieceType> {
+        vec![
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ]
+    }
+
+    /// FEN of the variant start position,
+    /// None uses the standard start position
+    fn start_fen(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// variant specific move validation, called after the
+    /// piece move is validated and before king check is validated
+    fn is_valid_move(
+        &self,
+        _piece_strategy: &dyn PieceMoveStrategy,
+        _move_validator: &MoveValidator,
+        _board: &Board,
+    ) -> bool {
+        true
+    }
+
+    /// move ends the game immediately, king check is not validated
+    fn is_winning_move(
+        &self,
+        _piece_strategy: &dyn PieceMoveStrategy,
+        _move_validator: &MoveValidator,
+        _board: &Board,
+    ) -> bool {
+        false
+    }
+
+    /// variant specific board changes, called after every move
+    /// made on the board, including moves used to validate check,
+    /// board changes are added to the move result effects
+    fn handle_move(&self, _move_result: &mut MoveResult, _board: &mut Board) {}
+
+    /// variant specific state changes, called once after
+    /// a move is completed with `Board::move_piece`
+    fn update_state(&self, _move_result: &MoveResult, _board: &mut Board) {}
+
+    /// winner by variant win condition,
+    /// checkmate is handled by the game for all variants
+    fn winner(&self, _board: &Board) -> Option<PieceColor> {
+        None
+    }
+
+    /// extra FEN fields written after the en passant field
+    fn write_fen_fields(&self, _board: &Board) -> Vec<String> {
+        vec![]
+    }
+
+    /// parse extra FEN field after the en passant field,
+    /// returns false if the field does not belong to the variant
+    fn parse_fen_field(&self, _field: &str, _board: &mut Board) -> ChessResult<bool> {
+        Ok(false)
+    }
+}
+
+pub struct VariantBuilder {}
+impl VariantBuilder {
+    pub fn new_variant_rules(variant: Variant) -> Box<dyn VariantRules> {
+        match variant {
+            Variant::Standard => Box::new(StandardRules {}),
+            Variant::KingOfTheHill => Box::new(KingOfTheHillRules {}),
+            Variant::ThreeCheck => Box::new(ThreeCheckRules {}),
+            Variant::Atomic => Box::new(AtomicRules {}),
+            Variant::Antichess => Box::new(AntichessRules {}),
+            Variant::Horde => Box::new(HordeRules {}),
+            Variant::Crazyhouse => Box::new(CrazyhouseRules {}),
+            Variant::Bughouse => Box::new(BughouseRules {}),
+        }
+    }
+}
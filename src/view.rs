@@ -0,0 +1,180 @@
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::error::ChessResult;
+use crate::pieces::piece::PieceColor;
+use crate::tile::{Tile, TileCoord, TileFile, TileRank};
+
+/// Edge of the board as shown on screen
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Board as seen by the player of the orientation color,
+/// the orientation color is at the bottom of the screen
+///
+/// screen row 0 is the top row and screen col 0 the left col,
+/// white sees rank 8 at the top and the a-file on the left,
+/// black sees rank 1 at the top and the h-file on the left
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoardView {
+    orientation: PieceColor,
+}
+
+#[wasm_bindgen]
+impl BoardView {
+    #[wasm_bindgen(constructor)]
+    pub fn new(orientation: PieceColor) -> Self {
+        Self { orientation }
+    }
+
+    pub fn orientation(&self) -> PieceColor {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, orientation: PieceColor) {
+        self.orientation = orientation
+    }
+
+    /// show the board from the other side
+    pub fn flip(&mut self) {
+        self.orientation = PieceColor::opposite_color(self.orientation)
+    }
+
+    /// board tiles in display order, top left tile first
+    pub fn js_tiles(&self, board: &Board) -> Result<Array, JsValue> {
+        let tiles = self
+            .coords()
+            .iter()
+            .map(|coord| {
+                board
+                    .tile_by_index(Board::tile_idx_from_coord(coord))
+                    .cloned()
+            })
+            .collect::<ChessResult<Vec<Tile>>>()?;
+        Ok(tiles.into_iter().map(JsValue::from).collect())
+    }
+
+    /// coord of the tile shown at screen row and col,
+    /// None if the screen position is off the board
+    pub fn coord_at(&self, screen_row: u8, screen_col: u8) -> Option<TileCoord> {
+        if screen_row > 7 || screen_col > 7 {
+            return None;
+        }
+
+        Some(TileCoord::new(
+            self.row_at(screen_row),
+            self.col_at(screen_col),
+        ))
+    }
+
+    /// screen row the coord is shown on
+    pub fn screen_row(&self, coord: &TileCoord) -> u8 {
+        match self.orientation {
+            PieceColor::White => 7 - coord.row(),
+            PieceColor::Black => coord.row(),
+        }
+    }
+
+    /// screen col the coord is shown on
+    pub fn screen_col(&self, coord: &TileCoord) -> u8 {
+        match self.orientation {
+            PieceColor::White => coord.col(),
+            PieceColor::Black => 7 - coord.col(),
+        }
+    }
+
+    /// rank or file labels along edge in screen order,
+    /// top to bottom for left and right, left to right for top and bottom
+    pub fn js_labels(&self, edge: BoardEdge) -> Array {
+        self.labels(edge)
+            .iter()
+            .map(|label| JsValue::from_str(label))
+            .collect()
+    }
+}
+
+impl BoardView {
+    /// all coords in display order, top left coord first
+    pub fn coords(&self) -> Vec<TileCoord> {
+        (0..8)
+            .flat_map(|screen_row| (0..8).map(move |screen_col| (screen_row, screen_col)))
+            .filter_map(|(screen_row, screen_col)| self.coord_at(screen_row, screen_col))
+            .collect()
+    }
+
+    /// rank or file labels along edge in screen order, see `js_labels`
+    pub fn labels(&self, edge: BoardEdge) -> Vec<String> {
+        (0..8)
+            .map(|screen_pos| match edge {
+                BoardEdge::Left | BoardEdge::Right => {
+                    TileRank::from(self.row_at(screen_pos)).to_string()
+                }
+                BoardEdge::Top | BoardEdge::Bottom => {
+                    TileFile::from(self.col_at(screen_pos)).to_string()
+                }
+            })
+            .collect()
+    }
+
+    // ---
+    // private methods
+    // ---
+
+    /// board row shown at screen row
+    fn row_at(&self, screen_row: u8) -> u8 {
+        match self.orientation {
+            PieceColor::White => 7 - screen_row,
+            PieceColor::Black => screen_row,
+        }
+    }
+
+    /// board col shown at screen col
+    fn col_at(&self, screen_col: u8) -> u8 {
+        match self.orientation {
+            PieceColor::White => screen_col,
+            PieceColor::Black => 7 - screen_col,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::pieces::piece::PieceColor;
+    use crate::tile::TileCoord;
+    use crate::view::{BoardEdge, BoardView};
+
+    #[test]
+    pub fn test_board_view() {
+        let mut view = BoardView::new(PieceColor::White);
+        let a8 = TileCoord::new(7, 0);
+
+        assert_eq!(Some(a8), view.coord_at(0, 0));
+        assert_eq!(a8, view.coords()[0]);
+        assert_eq!(None, view.coord_at(8, 0));
+        assert_eq!("87654321", view.labels(BoardEdge::Left).concat());
+        assert_eq!("abcdefgh", view.labels(BoardEdge::Bottom).concat());
+
+        // black sees h1 in the top left corner
+        view.flip();
+        assert_eq!(Some(TileCoord::new(0, 7)), view.coord_at(0, 0));
+        assert_eq!((7, 7), (view.screen_row(&a8), view.screen_col(&a8)));
+        assert_eq!("12345678", view.labels(BoardEdge::Right).concat());
+        assert_eq!("hgfedcba", view.labels(BoardEdge::Top).concat());
+
+        // screen position maps back to the same coord
+        for coord in view.coords() {
+            assert_eq!(
+                Some(coord),
+                view.coord_at(view.screen_row(&coord), view.screen_col(&coord))
+            );
+        }
+    }
+}
@@ -1,9 +1,8 @@
-import React, { useEffect, useState, useRef } from 'react';
-import { Tile as ITile } from 'chess-lib';
+import React, { useEffect, useMemo, useState, useRef } from 'react';
 
 import Tile from './Tile';
 
-import { rotateBoard } from '../../util/board';
+import { boardSnapshot, snapshotDisplayTiles } from '../../util/snapshot';
 import { TILE_SPACE } from '../../types/Board';
 import useBoardContext from '../../hooks/useBoardContext';
 import useGameContext from '../../hooks/useGameContext';
@@ -14,12 +13,13 @@ const Board = () => {
   const { tiles, board, boardDirection, setSelectedTile, setTiles } =
     useBoardContext();
   const { onlineGameState } = useGameContext();
-  const [localTiles, setLocalTiles] = useState<ITile[]>(tiles);
 
-  // local tiles used to keep state fresh
-  useEffect(() => {
-    setLocalTiles(rotateBoard(board.js_tiles(), boardDirection));
-  }, [tiles, boardDirection]);
+  // snapshot is re-read when tiles change to keep state fresh
+  const snapshot = useMemo(() => boardSnapshot(board), [board, tiles]);
+  const displayTiles = useMemo(
+    () => snapshotDisplayTiles(snapshot, boardDirection),
+    [snapshot, boardDirection]
+  );
 
   // remove selected tile if not click on the board
   // handle away from tile click,
@@ -29,6 +29,7 @@ const Board = () => {
       if (!divEl.current.contains(e.target as HTMLDivElement)) {
         setSelectedTile(null);
         board.clear_active_tiles();
+        board.clear_highlights();
         setTiles(board.js_tiles());
       }
     }
@@ -80,9 +81,8 @@ const Board = () => {
           }}
         />
       )}
-      {/* {tiles.map((tile, idx) => ( */}
-      {localTiles.map((tile, idx) => (
-        <Tile key={idx} tile={tile} />
+      {displayTiles.map((tile) => (
+        <Tile key={tile.index} tile={tile} />
       ))}
     </div>
   );
@@ -58,9 +58,12 @@ const BoardContainer = () => {
         const fromCoord = board.get_selected_piece_coord();
 
         if (fromCoord) {
+          // promote piece is not chosen yet,
+          // promotion moves return with is_promote_piece set
           const moveResult = board.pre_move_result(
             fromCoord,
-            selectedTile.coord()
+            selectedTile.coord(),
+            undefined
           );
 
           if (moveResult) {
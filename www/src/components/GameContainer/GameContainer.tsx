@@ -51,7 +51,7 @@ const GameContainer: React.FC<Props> = ({ children }) => {
   const { sendMoveMsg, updateApp, msgs } = useConnectionContext();
 
   const { loading, setLoading } = useLoadingContext();
-  const { board, setTiles, tiles, setBoardDirection, resetAll } =
+  const { board, setBoard, setTiles, tiles, setBoardDirection, resetAll } =
     useBoardContext();
   const {
     game,
@@ -76,8 +76,15 @@ const GameContainer: React.FC<Props> = ({ children }) => {
 
       const playerTurn = game.player_turn();
 
-      // add last move to game
-      handleGameStringMove(moveStr, playerTurn, board, game);
+      // add last move to game,
+      // board already made the move so reset it to the game board if rejected
+      if (!handleGameStringMove(moveStr, playerTurn, board, game)) {
+        const gameBoard = game.board();
+        game.set_player_turn(gameBoard.active_color());
+        setBoard(gameBoard);
+        setTiles(gameBoard.js_tiles());
+        return;
+      }
 
       // save moves to local session
       if (!onlineGameState) {
@@ -198,17 +205,22 @@ const GameContainer: React.FC<Props> = ({ children }) => {
       // get player turn and parse string to
       // move result
       const playerTurn = game.player_turn();
-      const moveRes = MoveParser.str_to_move_result(
-        maybeLastMoveMsg.content,
-        playerTurn
-      );
-
-      // update is_from_remote
-      // flag used to prevent local from
-      // resending move to server
-      moveRes.set_is_from_remote(true);
-
-      setLastMove(moveRes);
+      try {
+        const moveRes = MoveParser.str_to_move_result(
+          maybeLastMoveMsg.content,
+          playerTurn
+        );
+
+        // update is_from_remote
+        // flag used to prevent local from
+        // resending move to server
+        moveRes.set_is_from_remote(true);
+
+        setLastMove(moveRes);
+      } catch (e) {
+        console.log('There was an error parsing the move from the server!');
+        console.log(e);
+      }
     }
 
     const info = parseGameRemoteServerInfo(msgs);
@@ -1,14 +1,15 @@
 import React from 'react';
-import { Piece as IPiece, PieceType } from 'chess-lib';
+import { PieceColor, PieceType } from 'chess-lib';
 import { getPieceImage } from '../../util/piece';
 
 interface Props {
-  piece: IPiece;
+  pieceType: PieceType;
+  color: PieceColor;
   reverse: boolean;
   modalPiece?: boolean;
 }
 
-const Piece: React.FC<Props> = ({ modalPiece, piece, reverse }) => {
+const Piece: React.FC<Props> = ({ modalPiece, pieceType, color, reverse }) => {
   return (
     <div
       css={{
@@ -24,7 +25,7 @@ const Piece: React.FC<Props> = ({ modalPiece, piece, reverse }) => {
           width: modalPiece ? '100%' : '60%',
           height: modalPiece ? '100%' : '60%'
         }}
-        src={getPieceImage(piece, reverse)}
+        src={getPieceImage(pieceType, color, reverse)}
       />
     </div>
   );
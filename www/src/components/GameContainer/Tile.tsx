@@ -1,30 +1,24 @@
 import React, { useRef, useState } from 'react';
-import {
-  GameState,
-  Piece as IPiece,
-  PieceColor,
-  Tile as ITile,
-  TileCoord,
-  TileState
-} from 'chess-lib';
-import { useEffect } from 'react';
+import { GameState } from 'chess-lib';
 import useGameContext from '../../hooks/useGameContext';
 import Piece from './Piece';
 import { isPieceReverse } from '../../util/piece';
 import { TILE_HEIGHT } from '../../types/Board';
 import { parseTileColor, parseTileFilter } from '../../util/tile';
+import { SnapshotTile, isSnapshotTileHighlighted } from '../../util/snapshot';
 import useBoardContext from '../../hooks/useBoardContext';
 
 interface Props {
-  tile: ITile;
+  tile: SnapshotTile;
 }
 
 const Tile: React.FC<Props> = ({ tile }) => {
   const { game, onlineGameState } = useGameContext();
-  const { boardDirection, setSelectedTile } = useBoardContext();
+  const { board, boardDirection, setSelectedTile } = useBoardContext();
 
+  // only the clicked tile is read from the board
   const handleTileClick = () => {
-    setSelectedTile(tile);
+    setSelectedTile(board.tile_at_index(tile.index));
   };
 
   const curPlayerTurnAndPiece = (): boolean => {
@@ -32,16 +26,16 @@ const Tile: React.FC<Props> = ({ tile }) => {
       return false;
     }
 
-    if (tile.state() === TileState.Highlight) {
+    if (isSnapshotTileHighlighted(tile)) {
       return true;
     }
 
-    if (onlineGameState && game.player_color() !== tile.piece()?.color()) {
+    if (onlineGameState && game.player_color() !== tile.piece?.color) {
       return false;
     }
 
-    if (tile.piece()) {
-      if (game.player_turn() === tile.piece()?.color()) {
+    if (tile.piece) {
+      if (game.player_turn() === tile.piece.color) {
         return true;
       }
     }
@@ -67,13 +61,12 @@ const Tile: React.FC<Props> = ({ tile }) => {
       })}
     >
       {/* {tile.render()} */}
-      {tile.piece() && (
+      {tile.piece && (
         <Piece
-          piece={tile.piece() as IPiece}
-          reverse={isPieceReverse(
-            boardDirection,
-            tile.piece()?.color() as PieceColor
-          )}
+          key={tile.piece.id}
+          pieceType={tile.piece.pieceType}
+          color={tile.piece.color}
+          reverse={isPieceReverse(boardDirection, tile.piece.color)}
         />
       )}
     </div>
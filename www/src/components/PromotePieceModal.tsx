@@ -56,7 +56,12 @@ const PieceContainer: React.FC<Props> = ({ piece, handlePieceClick }) => {
       }}
       onClick={handlePieceClick}
     >
-      <Piece modalPiece piece={piece} reverse={false} />
+      <Piece
+        modalPiece
+        pieceType={piece.piece_type()}
+        color={piece.color()}
+        reverse={false}
+      />
     </div>
   );
 };
@@ -14,7 +14,8 @@ export const handleBoardPieceMove = (
   fromCoord: TileCoord,
   toCoord: TileCoord,
   board: Board,
-  game: Game
+  game: Game,
+  promotePieceType?: PieceType
 ): MoveResult | undefined => {
   // check if current player turn
   const curPlayerPiece = board.get_piece(fromCoord);
@@ -22,7 +23,8 @@ export const handleBoardPieceMove = (
   const piece = board.get_piece(fromCoord);
 
   if (piece) {
-    const moveResult = board.move_piece(fromCoord, toCoord);
+    // promote piece type is required when moving pawn to last rank
+    const moveResult = board.move_piece(fromCoord, toCoord, promotePieceType);
 
     // change player turn if piece moved
     if (moveResult) {
@@ -37,6 +39,7 @@ export const handleBoardPieceMove = (
     }
 
     board.clear_active_tiles();
+    board.clear_highlights();
     return moveResult;
   }
 };
@@ -2,7 +2,6 @@ import {
   Board,
   Game,
   GameState,
-  TileCoord,
   PieceColor,
   MoveResult,
   MoveParser
@@ -37,47 +36,49 @@ export const handleCheckmate = (
   }
 };
 
+// returns false if the game rejected the move, the board is left unchanged
 export const handleGameStringMove = (
   moveStr: string,
   pieceColor: PieceColor,
   board: Board,
   game: Game
-) => {
+): boolean => {
+  // write move to game first,
+  // game moves its own board and throws on an illegal move
+  try {
+    game.add_move(moveStr, pieceColor);
+  } catch (err) {
+    console.error(`move ${moveStr} rejected by game`, err);
+    return false;
+  }
+
   const moveResult: MoveResult = MoveParser.str_to_move_result(
     moveStr,
     pieceColor
   );
 
-  // make board move for white
-  handleBoardPieceMove(moveResult.from_coord, moveResult.to_coord, board, game);
-
-  // write move to game
-  game.add_move(moveStr, pieceColor);
+  // make board move, including promote piece if promotion
+  handleBoardPieceMove(
+    moveResult.from_coord,
+    moveResult.to_coord,
+    board,
+    game,
+    moveResult.promote_piece_type
+  );
 
-  // update board if promote piece
-  if (moveResult.is_promote_piece && moveResult.promote_piece_type) {
-    const coord = TileCoord.from_json(moveResult.to_coord.to_json());
-    board.set_new_tile(
-      coord,
-      moveResult.promote_piece_type,
-      moveResult.piece_color
-    );
-  }
+  return true;
 };
 
 export const handlePlaySavedMoves = (board: Board, game: Game) => {
   const savedGameMoves = getSavedGameMoves();
 
-  const movesSplit = MoveParser.js_split_all_moves(savedGameMoves);
-
-  for (const moveStrSet of movesSplit) {
-    const whiteMoveStr: string = moveStrSet[0];
-    const blackMoveStr: string | undefined = moveStrSet[1];
-
-    handleGameStringMove(whiteMoveStr, PieceColor.White, board, game);
+  // moves in ply order, saved games may start with black to move
+  const moveStrs: string[] = MoveParser.js_split_all_moves(savedGameMoves);
 
-    if (blackMoveStr) {
-      handleGameStringMove(blackMoveStr, PieceColor.Black, board, game);
+  for (const moveStr of moveStrs) {
+    // later moves depend on the rejected one
+    if (!handleGameStringMove(moveStr, game.board().active_color(), board, game)) {
+      break;
     }
   }
 };
@@ -1,74 +1,18 @@
-import { PieceColor, Tile, Game } from 'chess-lib';
+import { BoardEdge, BoardView, PieceColor, Tile, Game } from 'chess-lib';
 import { BorderSide } from '../types/Board';
 
-export const rotateBoard = (
-  tiles: Tile[],
-  boardDirection: PieceColor
-): Tile[] => {
-  if (boardDirection === PieceColor.White) {
-    const chunks: Tile[][] = [];
-    for (let i = 0; i < 8; i++) {
-      const offset = i * 8;
-      const chunk = tiles.slice(offset, offset + 8);
-      chunks.push(chunk);
-    }
-    return chunks.reverse().flatMap((tileArr) => tileArr);
-  } else {
-    const chunks: Tile[][] = [];
-    for (let i = 0; i < 8; i++) {
-      const offset = i * 8;
-      const chunk = tiles.slice(offset, offset + 8);
-      chunks.push(chunk);
-    }
-    return chunks
-      .map((tileArr) => tileArr.reverse())
-      .flatMap((tileArr) => tileArr);
-  }
+const BORDER_EDGES: Record<BorderSide, BoardEdge> = {
+  [BorderSide.Left]: BoardEdge.Left,
+  [BorderSide.Right]: BoardEdge.Right,
+  [BorderSide.Top]: BoardEdge.Top,
+  [BorderSide.Bottom]: BoardEdge.Bottom,
 };
 
 export const getBorderLabels = (
   boardDirection: PieceColor,
   borderSide: BorderSide
-) => {
-  const rankLabels = ['1', '2', '3', '4', '5', '6', '7', '8'];
-  const fileLabels = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
-
-  // render rank numbers in reverse order if direction is white
-  // white is default direction
-  const isReversed = boardDirection === PieceColor.White;
-
-  switch (borderSide) {
-    case BorderSide.Top:
-      if (!isReversed) {
-        return fileLabels.reverse();
-      } else {
-        return fileLabels;
-      }
-    case BorderSide.Bottom:
-      if (!isReversed) {
-        return fileLabels.reverse();
-      } else {
-        return fileLabels;
-      }
-
-    case BorderSide.Right:
-      if (isReversed) {
-        return rankLabels.reverse();
-      } else {
-        return rankLabels;
-      }
-
-    case BorderSide.Left:
-      if (isReversed) {
-        return rankLabels.reverse();
-      } else {
-        return rankLabels;
-      }
-
-    default:
-      return [''];
-  }
-};
+): string[] =>
+  new BoardView(boardDirection).js_labels(BORDER_EDGES[borderSide]);
 
 export const isPlayerTurn = (tile: Tile, game: Game): boolean => {
   let curPlayerTurn = game.player_turn();
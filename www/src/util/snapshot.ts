@@ -0,0 +1,119 @@
+import { Board, PieceColor, PieceType, TileColor, TileState } from 'chess-lib';
+
+// layout is documented on the `Snapshot` struct in src/snapshot.rs
+const SNAPSHOT_VERSION = 2;
+const SNAPSHOT_HEADER_LEN = 4;
+const SNAPSHOT_LEN = SNAPSHOT_HEADER_LEN + 64 * 5;
+const SNAPSHOT_NO_EN_PASSANT = 255;
+
+const PIECE_TYPES: Record<number, PieceType> = {
+  1: PieceType.Pawn,
+  2: PieceType.Knight,
+  3: PieceType.Bishop,
+  4: PieceType.Rook,
+  5: PieceType.Queen,
+  6: PieceType.King,
+};
+
+export interface SnapshotPiece {
+  id: number;
+  pieceType: PieceType;
+  color: PieceColor;
+}
+
+export interface SnapshotTile {
+  index: number;
+  color: TileColor;
+  state: TileState;
+  piece?: SnapshotPiece;
+}
+
+export interface BoardSnapshot {
+  activeColor: PieceColor;
+  castling: number;
+  enPassantIndex?: number;
+  tiles: SnapshotTile[];
+}
+
+// same fallback as the Rust `TileState::from`
+const tileState = (state: number): TileState =>
+  state <= TileState.Capture ? state : TileState.Unknown;
+
+// same as `Board::tile_color_from_coord`
+const tileColor = (index: number): TileColor =>
+  ((index >> 3) + (index & 7)) % 2 === 0 ? TileColor.Black : TileColor.White;
+
+// throws on snapshots the Rust `Snapshot::decode` rejects
+export const decodeSnapshot = (bytes: Uint8Array): BoardSnapshot => {
+  if (bytes.length !== SNAPSHOT_LEN) {
+    throw new Error(`snapshot must be ${SNAPSHOT_LEN} bytes`);
+  }
+
+  if (bytes[0] !== SNAPSHOT_VERSION) {
+    throw new Error(`unknown snapshot version ${bytes[0]}`);
+  }
+
+  if (bytes[1] !== 0 && bytes[1] !== 1) {
+    throw new Error(`invalid snapshot color ${bytes[1]}`);
+  }
+
+  const ids = new DataView(
+    bytes.buffer,
+    bytes.byteOffset + SNAPSHOT_HEADER_LEN + 64,
+    64 * 4
+  );
+  const tileBytes = bytes.subarray(SNAPSHOT_HEADER_LEN, SNAPSHOT_HEADER_LEN + 64);
+
+  const tiles = Array.from(tileBytes, (byte, index) => {
+    const pieceCode = byte & 0x0f;
+    const tile: SnapshotTile = {
+      index,
+      color: tileColor(index),
+      state: tileState(byte >> 4),
+    };
+
+    if (pieceCode !== 0) {
+      const pieceType = PIECE_TYPES[pieceCode & 7];
+      if (pieceType === undefined) {
+        throw new Error(`invalid snapshot piece code ${pieceCode}`);
+      }
+
+      tile.piece = {
+        id: ids.getUint32(index * 4, true),
+        pieceType,
+        color: pieceCode & 8 ? PieceColor.Black : PieceColor.White,
+      };
+    }
+
+    return tile;
+  });
+
+  return {
+    activeColor: bytes[1] === 0 ? PieceColor.White : PieceColor.Black,
+    castling: bytes[2],
+    enPassantIndex: bytes[3] === SNAPSHOT_NO_EN_PASSANT ? undefined : bytes[3],
+    tiles,
+  };
+};
+
+export const boardSnapshot = (board: Board): BoardSnapshot =>
+  decodeSnapshot(board.to_snapshot());
+
+export const isSnapshotTileHighlighted = (tile: SnapshotTile): boolean =>
+  tile.state === TileState.Highlight || tile.state === TileState.Capture;
+
+// tiles in display order with the board direction color at the bottom,
+// same order as `BoardView::js_tiles`
+export const snapshotDisplayTiles = (
+  snapshot: BoardSnapshot,
+  boardDirection: PieceColor
+): SnapshotTile[] =>
+  Array.from({ length: 64 }, (_, screenIndex) => {
+    const screenRow = screenIndex >> 3;
+    const screenCol = screenIndex & 7;
+    const index =
+      boardDirection === PieceColor.White
+        ? (7 - screenRow) * 8 + screenCol
+        : screenRow * 8 + (7 - screenCol);
+    return snapshot.tiles[index];
+  });
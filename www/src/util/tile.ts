@@ -1,17 +1,18 @@
 import { Theme } from '@emotion/react';
-import { Tile, TileState, TileColor } from 'chess-lib';
+import { TileState, TileColor } from 'chess-lib';
+import { SnapshotTile, isSnapshotTileHighlighted } from './snapshot';
 
-export const parseTileColor = (tile: Tile, theme: Theme): string => {
-  switch (tile.color()) {
+export const parseTileColor = (tile: SnapshotTile, theme: Theme): string => {
+  switch (tile.color) {
     case TileColor.White:
-      if (tile.state() === TileState.Highlight) {
+      if (isSnapshotTileHighlighted(tile)) {
         return theme.colors.board.whiteGradient;
       } else {
         return theme.colors.board.white;
       }
 
     case TileColor.Black:
-      if (tile.state() === TileState.Highlight) {
+      if (isSnapshotTileHighlighted(tile)) {
         return theme.colors.board.blackGradient;
       } else {
         return theme.colors.board.black;
@@ -21,8 +22,8 @@ export const parseTileColor = (tile: Tile, theme: Theme): string => {
   }
 };
 
-export const parseTileFilter = (tile: Tile): string => {
-  switch (tile.state()) {
+export const parseTileFilter = (tile: SnapshotTile): string => {
+  switch (tile.state) {
     case TileState.Active:
       return 'grayscale(80%)';
 
@@ -87,8 +87,9 @@ export const isPlayerTurn = (tile: Tile, game: Game): boolean => {
 };
 
 export const highlightMoves = (tile: Tile, board: Board) => {
-  // clear all current active tiles
+  // clear all current active tiles and highlights
   board.clear_active_tiles();
+  board.clear_highlights();
 
   const piece = tile.piece();
 
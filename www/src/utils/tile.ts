@@ -4,14 +4,14 @@ import { Tile, TileState, TileColor } from 'chess-lib';
 export const parseTileColor = (tile: Tile, theme: Theme): string => {
   switch (tile.color()) {
     case TileColor.White:
-      if (tile.state() === TileState.Highlight) {
+      if (tile.is_highlighted()) {
         return theme.colors.board.whiteGradient;
       } else {
         return theme.colors.board.white;
       }
 
     case TileColor.Black:
-      if (tile.state() === TileState.Highlight) {
+      if (tile.is_highlighted()) {
         return theme.colors.board.blackGradient;
       } else {
         return theme.colors.board.black;